@@ -0,0 +1,21 @@
+#[derive(Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Whether to cache anything at all.
+    pub enabled: bool,
+    /// The resource types to cache, such as `guild` or `message`, every type
+    /// by default.
+    pub resource_types: Option<Vec<String>>,
+    /// The number of messages to cache per channel.
+    pub message_cache_size: Option<usize>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            resource_types: None,
+            message_cache_size: None,
+        }
+    }
+}
@@ -1,7 +1,12 @@
+use std::path::PathBuf;
+
 use secrecy::SecretString;
 
 #[derive(Debug, serde::Deserialize)]
 pub struct Config {
     /// The token of the Discord bot.
     pub token: SecretString,
+    /// The file gateway sessions are saved to on shutdown, and resumed from
+    /// on startup.
+    pub session_path: Option<PathBuf>,
 }
@@ -5,6 +5,7 @@ use figment::{
     providers::{Env, Format as _, Yaml},
 };
 
+pub mod cache;
 pub mod discord;
 
 /// Configuration options.
@@ -12,6 +13,9 @@ pub mod discord;
 pub struct Config {
     /// Discord configuration options.
     pub discord: discord::Config,
+    /// Cache configuration options.
+    #[serde(default)]
+    pub cache: cache::Config,
 }
 
 impl Config {
@@ -38,6 +42,8 @@ pub enum ConfigParseError {
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
+
     use figment::Jail;
     use secrecy::ExposeSecret as _;
 
@@ -56,6 +62,78 @@ mod tests {
 
             let config = Config::parse("config.yaml").unwrap();
             assert_eq!(config.discord.token.expose_secret(), "meow");
+            assert_eq!(config.discord.session_path, None);
+            assert!(config.cache.enabled);
+            assert_eq!(config.cache.resource_types, None);
+            assert_eq!(config.cache.message_cache_size, None);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_parse_session_path() {
+        Jail::expect_with(|jail| {
+            jail.create_file(
+                "config.yaml",
+                r"
+                discord:
+                    token: meow
+                    session_path: sessions.json
+                ",
+            )?;
+
+            let config = Config::parse("config.yaml").unwrap();
+            assert_eq!(
+                config.discord.session_path.as_deref(),
+                Some(Path::new("sessions.json"))
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_parse_cache_config() {
+        Jail::expect_with(|jail| {
+            jail.create_file(
+                "config.yaml",
+                r"
+                discord:
+                    token: meow
+                cache:
+                    resource_types: [guild, member]
+                    message_cache_size: 50
+                ",
+            )?;
+
+            let config = Config::parse("config.yaml").unwrap();
+            assert!(config.cache.enabled);
+            assert_eq!(
+                config.cache.resource_types.as_deref(),
+                Some(["guild".to_owned(), "member".to_owned()].as_slice())
+            );
+            assert_eq!(config.cache.message_cache_size, Some(50));
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_parse_disabled_cache() {
+        Jail::expect_with(|jail| {
+            jail.create_file(
+                "config.yaml",
+                r"
+                discord:
+                    token: meow
+                cache:
+                    enabled: false
+                ",
+            )?;
+
+            let config = Config::parse("config.yaml").unwrap();
+            assert!(!config.cache.enabled);
 
             Ok(())
         });
@@ -0,0 +1,210 @@
+use std::sync::Arc;
+
+use core::any::Any;
+
+use twilight_cache_inmemory::{InMemoryCache, ResourceType};
+use twilight_gateway::{Event, EventTypeFlags};
+
+/// Events the cache updates each resource type from, received whether or not
+/// the event handlers use them.
+///
+/// Guilds are cached whole on `GUILD_CREATE` and evicted on `GUILD_DELETE`, so
+/// those are received for every resource type.
+const CACHE_EVENT_FLAGS: [(ResourceType, EventTypeFlags); 15] = [
+    (
+        ResourceType::CHANNEL,
+        EventTypeFlags::CHANNEL_CREATE
+            .union(EventTypeFlags::CHANNEL_DELETE)
+            .union(EventTypeFlags::CHANNEL_PINS_UPDATE)
+            .union(EventTypeFlags::CHANNEL_UPDATE)
+            .union(EventTypeFlags::THREAD_CREATE)
+            .union(EventTypeFlags::THREAD_DELETE)
+            .union(EventTypeFlags::THREAD_LIST_SYNC)
+            .union(EventTypeFlags::THREAD_UPDATE),
+    ),
+    (ResourceType::EMOJI, EventTypeFlags::GUILD_EMOJIS_UPDATE),
+    (ResourceType::GUILD, EventTypeFlags::GUILD_UPDATE),
+    (
+        ResourceType::MEMBER,
+        EventTypeFlags::MEMBER_ADD
+            .union(EventTypeFlags::MEMBER_CHUNK)
+            .union(EventTypeFlags::MEMBER_REMOVE)
+            .union(EventTypeFlags::MEMBER_UPDATE)
+            .union(EventTypeFlags::MESSAGE_CREATE)
+            .union(EventTypeFlags::VOICE_STATE_UPDATE),
+    ),
+    (
+        ResourceType::MESSAGE,
+        EventTypeFlags::MESSAGE_CREATE
+            .union(EventTypeFlags::MESSAGE_DELETE)
+            .union(EventTypeFlags::MESSAGE_DELETE_BULK)
+            .union(EventTypeFlags::MESSAGE_UPDATE),
+    ),
+    (
+        ResourceType::PRESENCE,
+        EventTypeFlags::MEMBER_CHUNK.union(EventTypeFlags::PRESENCE_UPDATE),
+    ),
+    (
+        ResourceType::REACTION,
+        EventTypeFlags::REACTION_ADD
+            .union(EventTypeFlags::REACTION_REMOVE)
+            .union(EventTypeFlags::REACTION_REMOVE_ALL)
+            .union(EventTypeFlags::REACTION_REMOVE_EMOJI),
+    ),
+    (
+        ResourceType::ROLE,
+        EventTypeFlags::ROLE_CREATE
+            .union(EventTypeFlags::ROLE_DELETE)
+            .union(EventTypeFlags::ROLE_UPDATE),
+    ),
+    (ResourceType::USER_CURRENT, EventTypeFlags::USER_UPDATE),
+    (
+        ResourceType::USER,
+        EventTypeFlags::MEMBER_ADD
+            .union(EventTypeFlags::MEMBER_CHUNK)
+            .union(EventTypeFlags::MEMBER_UPDATE)
+            .union(EventTypeFlags::MESSAGE_CREATE),
+    ),
+    (
+        ResourceType::VOICE_STATE,
+        EventTypeFlags::VOICE_STATE_UPDATE,
+    ),
+    (
+        ResourceType::STAGE_INSTANCE,
+        EventTypeFlags::STAGE_INSTANCE_CREATE
+            .union(EventTypeFlags::STAGE_INSTANCE_DELETE)
+            .union(EventTypeFlags::STAGE_INSTANCE_UPDATE),
+    ),
+    (
+        ResourceType::INTEGRATION,
+        EventTypeFlags::INTEGRATION_CREATE
+            .union(EventTypeFlags::INTEGRATION_DELETE)
+            .union(EventTypeFlags::INTEGRATION_UPDATE),
+    ),
+    (ResourceType::STICKER, EventTypeFlags::GUILD_STICKERS_UPDATE),
+    (
+        ResourceType::GUILD_SCHEDULED_EVENT,
+        EventTypeFlags::GUILD_SCHEDULED_EVENT_CREATE
+            .union(EventTypeFlags::GUILD_SCHEDULED_EVENT_DELETE)
+            .union(EventTypeFlags::GUILD_SCHEDULED_EVENT_UPDATE)
+            .union(EventTypeFlags::GUILD_SCHEDULED_EVENT_USER_ADD)
+            .union(EventTypeFlags::GUILD_SCHEDULED_EVENT_USER_REMOVE),
+    ),
+];
+
+/// A cache the client updates from the events its shards receive, and hands
+/// to the handlers through [`Context::cache`](crate::Context::cache).
+///
+/// Implemented by [`InMemoryCache`], and set with
+/// [`ClientBuilder::cache`](crate::client::ClientBuilder::cache).
+pub trait Cache: Send + Sync + 'static {
+    /// Updates the cache from an event, before the event is dispatched.
+    fn update(&self, event: &Event);
+
+    /// The events the cache needs to stay up to date, received whether or not
+    /// the event handlers use them.
+    fn event_flags(&self) -> EventTypeFlags;
+}
+
+impl Cache for InMemoryCache {
+    fn update(&self, event: &Event) {
+        Self::update(self, event);
+    }
+
+    fn event_flags(&self) -> EventTypeFlags {
+        cache_event_flags(self.config().resource_types())
+    }
+}
+
+/// A cache shared between the client, which updates it, and the handlers,
+/// which retrieve it by its type.
+#[derive(Clone)]
+pub struct SharedCache {
+    cache: Arc<dyn Cache>,
+    value: Arc<dyn Any + Send + Sync>,
+}
+
+impl SharedCache {
+    #[must_use]
+    pub fn new<C: Cache>(cache: Arc<C>) -> Self {
+        Self {
+            cache: cache.clone(),
+            value: cache,
+        }
+    }
+
+    /// The cache, if it's of type `C`.
+    #[must_use]
+    pub fn get<C: Cache>(&self) -> Option<Arc<C>> {
+        Arc::clone(&self.value).downcast().ok()
+    }
+
+    pub fn update(&self, event: &Event) {
+        self.cache.update(event);
+    }
+
+    #[must_use]
+    pub fn event_flags(&self) -> EventTypeFlags {
+        self.cache.event_flags()
+    }
+}
+
+impl core::fmt::Debug for SharedCache {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SharedCache").finish_non_exhaustive()
+    }
+}
+
+/// Returns the events the cache needs to keep the resource types up to date.
+fn cache_event_flags(resource_types: ResourceType) -> EventTypeFlags {
+    if resource_types.is_empty() {
+        return EventTypeFlags::empty();
+    }
+
+    CACHE_EVENT_FLAGS
+        .into_iter()
+        .filter(|(resource_type, _)| resource_types.intersects(*resource_type))
+        .fold(
+            EventTypeFlags::GUILD_CREATE
+                | EventTypeFlags::GUILD_DELETE
+                | EventTypeFlags::UNAVAILABLE_GUILD,
+            |event_flags, (_, resource_event_flags)| event_flags | resource_event_flags,
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct GuildCount;
+
+    impl Cache for GuildCount {
+        fn update(&self, _event: &Event) {}
+
+        fn event_flags(&self) -> EventTypeFlags {
+            EventTypeFlags::GUILD_CREATE
+        }
+    }
+
+    #[test]
+    fn test_get_cache_by_type() {
+        let cache = SharedCache::new(Arc::new(GuildCount));
+
+        assert!(cache.get::<GuildCount>().is_some());
+        assert!(cache.get::<InMemoryCache>().is_none());
+        assert_eq!(cache.event_flags(), EventTypeFlags::GUILD_CREATE);
+    }
+
+    #[test]
+    fn test_in_memory_cache_event_flags() {
+        let cache = InMemoryCache::builder()
+            .resource_types(ResourceType::ROLE)
+            .build();
+        let event_flags = Cache::event_flags(&cache);
+
+        assert!(event_flags.contains(EventTypeFlags::GUILD_CREATE | EventTypeFlags::ROLE_CREATE));
+        assert!(!event_flags.contains(EventTypeFlags::MESSAGE_CREATE));
+        assert!(cache_event_flags(ResourceType::empty()).is_empty());
+    }
+}
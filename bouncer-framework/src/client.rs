@@ -1,27 +1,103 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
+
+use core::{num::NonZeroUsize, ops::Range, time::Duration};
 
 use secrecy::{ExposeSecret as _, SecretString};
-use twilight_cache_inmemory::InMemoryCache;
-use twilight_gateway::{Intents, Shard, ShardId, StreamExt as _};
+use tokio::{sync::watch, task::JoinSet};
+use twilight_cache_inmemory::{InMemoryCache, ResourceType};
+use twilight_gateway::{
+    CloseFrame, Config, ConfigBuilder, Event, EventTypeFlags, Intents, Shard, ShardId, ShardState,
+    StreamExt as _,
+    error::{ReceiveMessageError, ReceiveMessageErrorType, StartRecommendedError},
+};
 use twilight_http::Client as HttpClient;
+use twilight_model::application::interaction::Interaction;
 
 use crate::{
+    cache::{Cache, SharedCache},
+    collector::Collectors,
+    command::CommandRegistry,
+    component::ComponentRegistry,
     context::Context,
-    event_handler::{EventExt as _, EventHandler},
+    data::DataMap,
+    dispatcher::{EventDispatcher, MAX_CONCURRENT_EVENTS},
+    event_handler::EventHandler,
+    exts::interaction::InteractionResponses,
+    middleware::Middleware,
+    session::{SessionStore, ShardSession},
+    shard::{ShardLifecycle, ShardStatuses},
+    shutdown::{InFlightTasks, SHUTDOWN_TIMEOUT, ShutdownHandle},
 };
 
+/// Intents Discord only grants to bots enabling them in the developer portal,
+/// and which are therefore never requested on the event handlers' behalf.
+const PRIVILEGED_INTENTS: Intents = Intents::GUILD_MEMBERS
+    .union(Intents::GUILD_PRESENCES)
+    .union(Intents::MESSAGE_CONTENT);
+
+/// Events received to keep the shard statuses up to date and notify the event
+/// handlers of lifecycle changes, whether or not they use the events.
+///
+/// Close events are always received, so aren't included.
+const SHARD_STATUS_EVENT_FLAGS: EventTypeFlags = EventTypeFlags::GATEWAY_HEARTBEAT_ACK
+    .union(EventTypeFlags::GATEWAY_HELLO)
+    .union(EventTypeFlags::GATEWAY_RECONNECT)
+    .union(EventTypeFlags::GATEWAY_INVALIDATE_SESSION)
+    .union(EventTypeFlags::READY)
+    .union(EventTypeFlags::RESUMED);
+
 pub struct Client {
-    shard: Shard,
+    config: Config,
+    shard_range: Option<ShardRange>,
+    shutdown: ShutdownHandle,
+    shutdown_timeout: Duration,
+    session_store: Option<Box<dyn SessionStore>>,
+    event_loop: EventLoop,
+}
+
+/// A range of shards out of a total, for running a cluster of them.
+#[derive(Debug, Clone)]
+struct ShardRange {
+    numbers: Range<u32>,
+    total: u32,
+}
+
+/// The state shared by the tasks running each shard.
+#[derive(Clone)]
+struct EventLoop {
+    event_flags: EventTypeFlags,
     http: Arc<HttpClient>,
-    cache: Arc<InMemoryCache>,
-    event_handler: Box<dyn EventHandler>,
+    cache: Option<SharedCache>,
+    interaction_responses: Arc<InteractionResponses>,
+    collectors: Arc<Collectors>,
+    shard_statuses: Arc<ShardStatuses>,
+    data: Arc<DataMap>,
+    max_reconnect_attempts: Option<u8>,
+    shutdown: watch::Receiver<bool>,
+    in_flight: Arc<InFlightTasks>,
+    dispatcher: Arc<EventDispatcher>,
+    command_registry: Option<Arc<CommandRegistry>>,
+    component_registry: Option<Arc<ComponentRegistry>>,
 }
 
 pub struct ClientBuilder {
     http: HttpClient,
-    shard_id: ShardId,
+    shard_range: Option<ShardRange>,
     intents: Intents,
-    event_handler: Option<Box<dyn EventHandler>>,
+    cache: Option<SharedCache>,
+    cache_enabled: bool,
+    cache_resource_types: ResourceType,
+    message_cache_size: Option<usize>,
+    data: DataMap,
+    event_handlers: Vec<Box<dyn EventHandler>>,
+    middlewares: Vec<Box<dyn Middleware>>,
+    max_concurrent_events: NonZeroUsize,
+    ordered_guild_events: bool,
+    max_reconnect_attempts: Option<u8>,
+    shutdown_timeout: Duration,
+    session_store: Option<Box<dyn SessionStore>>,
+    command_registry: Option<CommandRegistry>,
+    component_registry: Option<ComponentRegistry>,
 }
 
 impl Client {
@@ -29,34 +105,286 @@ impl Client {
     pub fn builder(token: &SecretString) -> ClientBuilder {
         ClientBuilder {
             http: HttpClient::new(token.expose_secret().to_owned()),
-            shard_id: ShardId::ONE,
+            shard_range: None,
             intents: Intents::empty(),
-            event_handler: None,
+            cache: None,
+            cache_enabled: true,
+            cache_resource_types: ResourceType::all(),
+            message_cache_size: None,
+            data: DataMap::default(),
+            event_handlers: Vec::new(),
+            middlewares: Vec::new(),
+            max_concurrent_events: MAX_CONCURRENT_EVENTS,
+            ordered_guild_events: false,
+            max_reconnect_attempts: None,
+            shutdown_timeout: SHUTDOWN_TIMEOUT,
+            session_store: None,
+            command_registry: None,
+            component_registry: None,
         }
     }
 
-    pub async fn start(&mut self) {
-        while let Some(event) = self
-            .shard
-            .next_event(self.event_handler.used_event_flags())
+    /// Returns a handle to shut the client down with, once started.
+    #[must_use]
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
+    /// Connects the shards and dispatches their events, each shard on its own
+    /// task, until all of them have stopped.
+    ///
+    /// Shards resume the sessions in the session store, if any. Once stopped,
+    /// their sessions are saved to it, and the in-flight handlers are waited
+    /// for, up to the shutdown timeout, before the event handlers' shutdown
+    /// hooks are run.
+    ///
+    /// When a shard stops on an error, the others are shut down, and the
+    /// error is returned once they have.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ClientError::RecommendedShards`] error if no shard range is set and
+    /// the recommended shard count couldn't be fetched.
+    /// Returns a [`ClientError::FatallyClosed`] error if Discord closed a shard for good,
+    /// such as for an invalid token, disallowed intents or an invalid shard.
+    /// Returns a [`ClientError::Reconnect`] error if a shard ran out of reconnect attempts.
+    pub async fn start(&self) -> Result<(), ClientError> {
+        let sessions = self.load_sessions().await;
+        let configure_shard = |shard_id, builder: ConfigBuilder| {
+            let Some(shard_session) = sessions.get(&shard_id) else {
+                return builder.build();
+            };
+
+            let builder = builder.session(shard_session.session.clone());
+            match &shard_session.resume_url {
+                Some(resume_url) => builder.resume_url(resume_url.clone()).build(),
+                None => builder.build(),
+            }
+        };
+
+        let shards = if let Some(shard_range) = &self.shard_range {
+            twilight_gateway::create_iterator(
+                shard_range.numbers.clone(),
+                shard_range.total,
+                self.config.clone(),
+                configure_shard,
+            )
+            .collect::<Vec<_>>()
+        } else {
+            twilight_gateway::create_recommended(
+                &self.event_loop.http,
+                self.config.clone(),
+                configure_shard,
+            )
+            .await?
+            .collect()
+        };
+        tracing::info!(shards = shards.len(), "starting shards");
+
+        let mut shard_tasks = JoinSet::new();
+        for shard in shards {
+            shard_tasks.spawn(self.event_loop.clone().run(shard));
+        }
+
+        let mut sessions = Vec::new();
+        let mut result = Ok(());
+        while let Some(shard_result) = shard_tasks.join_next().await {
+            match shard_result {
+                Ok(Ok(session)) => sessions.extend(session),
+                Ok(Err(error)) => {
+                    tracing::error!(source = ?error, "shard stopped");
+                    if result.is_ok() {
+                        self.shutdown.shutdown();
+                        result = Err(error);
+                    }
+                }
+                Err(error) => tracing::error!(source = ?error, "shard task failed"),
+            }
+        }
+        self.save_sessions(sessions).await;
+
+        let in_flight = self.event_loop.in_flight.wait();
+        if tokio::time::timeout(self.shutdown_timeout, in_flight)
             .await
+            .is_err()
         {
+            tracing::warn!("timed out waiting for in-flight handlers");
+        }
+        self.event_loop.dispatcher.shutdown().await;
+
+        result
+    }
+
+    async fn load_sessions(&self) -> HashMap<ShardId, ShardSession> {
+        let Some(session_store) = &self.session_store else {
+            return HashMap::new();
+        };
+
+        match session_store.load().await {
+            Ok(sessions) => sessions
+                .into_iter()
+                .map(|session| (session.shard_id, session))
+                .collect(),
+            Err(error) => {
+                tracing::warn!(source = ?error, "failed to load sessions, identifying instead");
+
+                HashMap::new()
+            }
+        }
+    }
+
+    async fn save_sessions(&self, sessions: Vec<ShardSession>) {
+        let Some(session_store) = &self.session_store else {
+            return;
+        };
+
+        if let Err(error) = session_store.save(sessions).await {
+            tracing::warn!(source = ?error, "failed to save sessions");
+        }
+    }
+}
+
+impl EventLoop {
+    /// Runs a shard until it stops, returning its session to resume if it was
+    /// shut down.
+    async fn run(mut self, mut shard: Shard) -> Result<Option<ShardSession>, ClientError> {
+        let shard_id = shard.id();
+        let sender = shard.sender();
+        let mut is_closing = false;
+        let mut close_code = None;
+
+        loop {
+            let event = tokio::select! {
+                event = shard.next_event(self.event_flags) => event,
+                _ = self.shutdown.wait_for(|&is_shutdown| is_shutdown), if !is_closing => {
+                    is_closing = true;
+                    // Sending the close makes the shard yield a close event,
+                    // but a disconnected shard would reconnect before sending
+                    // it, so is stopped as is.
+                    if matches!(shard.state(), ShardState::Disconnected { .. })
+                        || sender.close(CloseFrame::RESUME).is_err()
+                    {
+                        break;
+                    }
+
+                    continue;
+                }
+            };
+            let Some(event) = event else {
+                break;
+            };
+            self.shard_statuses.update(&shard);
+
             match event {
+                Ok(Event::GatewayClose(_)) if is_closing => break,
                 Ok(event) => {
-                    self.cache.update(&event);
-                    event
-                        .dispatch(self.create_context(), &*self.event_handler)
-                        .await;
-                }
-                Err(error) => {
-                    tracing::error!(source = ?error, "error receiving event");
+                    if let Some(cache) = &self.cache {
+                        cache.update(&event);
+                    }
+
+                    let context = self.create_context(shard_id);
+                    if let Event::GatewayClose(Some(close_frame)) = &event {
+                        close_code = Some(close_frame.code);
+                    }
+                    if let Some(lifecycle) = ShardLifecycle::from_event(&event) {
+                        self.dispatcher
+                            .dispatch_lifecycle(context.clone(), lifecycle);
+                    }
+                    if let Event::InteractionCreate(interaction) = &event {
+                        if !self.collectors.dispatch(interaction) {
+                            self.spawn_interaction_handler(context.clone(), interaction.0.clone());
+                        }
+                    }
+
+                    self.dispatcher.dispatch(context, event);
                 }
+                Err(error) => self.handle_receive_error(&shard, error)?,
             }
         }
+
+        self.shard_statuses.update(&shard);
+
+        if is_closing {
+            Ok(ShardSession::from_shard(&shard))
+        } else {
+            // The shard only stops by itself when fatally closed.
+            Err(ClientError::FatallyClosed {
+                shard_id,
+                close_code,
+            })
+        }
+    }
+
+    /// Skips events that failed to be received, and notifies the event
+    /// handlers of failed reconnects until running out of attempts.
+    ///
+    /// The shard backs off before each attempt, doubling the delay every time.
+    fn handle_receive_error(
+        &self,
+        shard: &Shard,
+        error: ReceiveMessageError,
+    ) -> Result<(), ClientError> {
+        let shard_id = shard.id();
+
+        if !matches!(error.kind(), ReceiveMessageErrorType::Reconnect) {
+            tracing::warn!(shard = %shard_id, source = ?error, "failed to receive event");
+
+            return Ok(());
+        }
+
+        let ShardState::Disconnected {
+            reconnect_attempts: attempt,
+        } = shard.state()
+        else {
+            return Ok(());
+        };
+        if self
+            .max_reconnect_attempts
+            .is_some_and(|max_reconnect_attempts| attempt >= max_reconnect_attempts)
+        {
+            return Err(ClientError::Reconnect {
+                shard_id,
+                source: error,
+            });
+        }
+
+        tracing::warn!(shard = %shard_id, attempt, source = ?error, "failed to reconnect, retrying");
+        self.dispatcher.dispatch_lifecycle(
+            self.create_context(shard_id),
+            ShardLifecycle::Reconnecting { attempt },
+        );
+
+        Ok(())
+    }
+
+    /// Routes an interaction to the registries on its own task, so handlers
+    /// waiting on a collector don't hold up the events they're waiting for.
+    fn spawn_interaction_handler(&self, context: Context, interaction: Interaction) {
+        let command_registry = self.command_registry.clone();
+        let component_registry = self.component_registry.clone();
+        let in_flight = self.in_flight.start();
+
+        tokio::spawn(async move {
+            if let Some(command_registry) = command_registry {
+                command_registry.handle(&context, &interaction).await;
+            }
+            if let Some(component_registry) = component_registry {
+                component_registry.handle(&context, &interaction).await;
+            }
+            drop(in_flight);
+        });
     }
 
-    fn create_context(&self) -> Context {
-        Context::new(self.http.clone(), self.cache.clone())
+    fn create_context(&self, shard_id: ShardId) -> Context {
+        Context::new(
+            self.http.clone(),
+            self.cache.clone(),
+            self.interaction_responses.clone(),
+            self.collectors.clone(),
+            self.shard_statuses.clone(),
+            self.data.clone(),
+            shard_id,
+        )
     }
 }
 
@@ -64,29 +392,104 @@ impl ClientBuilder {
     /// # Errors
     ///
     /// Returns a [`ClientBuilderErrorType::MissingHTTPClientToken`] error if the HTTP client doesn't have a token set.
-    /// Returns a [`ClientBuilderErrorType::MissingEventHandler`] error if the event handler is not set.
+    /// Returns a [`ClientBuilderErrorType::MissingEventHandler`] error if no event handler is set.
+    /// Returns a [`ClientBuilderErrorType::InvalidShardRange`] error if the shard range is empty or exceeds its total.
     pub fn try_build(self) -> Result<Client, ClientBuilderError> {
+        if self.event_handlers.is_empty() {
+            return Err(ClientBuilderError::MissingEventHandler);
+        }
+        if let Some(ShardRange { numbers, total }) = &self.shard_range {
+            if numbers.is_empty() || numbers.end > *total {
+                return Err(ClientBuilderError::InvalidShardRange {
+                    numbers: numbers.clone(),
+                    total: *total,
+                });
+            }
+        }
+
+        let required_intents = self
+            .event_handlers
+            .iter()
+            .fold(Intents::empty(), |intents, event_handler| {
+                intents | event_handler.required_intents()
+            });
+        let missing_privileged_intents = required_intents
+            .intersection(PRIVILEGED_INTENTS)
+            .difference(self.intents);
+        if !missing_privileged_intents.is_empty() {
+            tracing::warn!(
+                intents = ?missing_privileged_intents,
+                "event handlers need privileged intents that weren't requested"
+            );
+        }
+        let intents = self.intents | required_intents.difference(PRIVILEGED_INTENTS);
+
         let http = Arc::new(self.http);
-        let shard = Shard::new(
-            self.shard_id,
+        let config = Config::new(
             http.token()
                 .ok_or(ClientBuilderError::MissingHTTPClientToken)?
                 .to_owned(),
-            self.intents,
+            intents,
+        );
+        let cache = self.cache_enabled.then(|| {
+            self.cache.unwrap_or_else(|| {
+                let cache = InMemoryCache::builder().resource_types(self.cache_resource_types);
+                let cache = match self.message_cache_size {
+                    Some(message_cache_size) => cache.message_cache_size(message_cache_size),
+                    None => cache,
+                };
+
+                SharedCache::new(Arc::new(cache.build()))
+            })
+        });
+        let (shutdown, shutdown_receiver) = ShutdownHandle::new();
+        let in_flight = Arc::<InFlightTasks>::default();
+        // Interactions are needed by the registries and collectors, whether or
+        // not the event handlers use them.
+        let event_flags = self.event_handlers.iter().fold(
+            EventTypeFlags::INTERACTION_CREATE
+                | SHARD_STATUS_EVENT_FLAGS
+                | cache
+                    .as_ref()
+                    .map_or(EventTypeFlags::empty(), SharedCache::event_flags),
+            |event_flags, event_handler| event_flags | event_handler.used_event_flags(),
         );
-        let cache = Arc::new(InMemoryCache::new());
-        let event_handler = self
-            .event_handler
-            .ok_or(ClientBuilderError::MissingEventHandler)?;
 
         Ok(Client {
-            shard,
-            http,
-            cache,
-            event_handler,
+            config,
+            shard_range: self.shard_range,
+            shutdown,
+            shutdown_timeout: self.shutdown_timeout,
+            session_store: self.session_store,
+            event_loop: EventLoop {
+                event_flags,
+                http,
+                cache,
+                interaction_responses: Arc::default(),
+                collectors: Arc::default(),
+                shard_statuses: Arc::default(),
+                data: Arc::new(self.data),
+                max_reconnect_attempts: self.max_reconnect_attempts,
+                shutdown: shutdown_receiver,
+                in_flight: in_flight.clone(),
+                dispatcher: Arc::new(EventDispatcher::new(
+                    self.event_handlers,
+                    self.middlewares,
+                    self.max_concurrent_events,
+                    in_flight,
+                    self.ordered_guild_events,
+                )),
+                command_registry: self.command_registry.map(Arc::new),
+                component_registry: self.component_registry.map(Arc::new),
+            },
         })
     }
 
+    /// Sets the intents to request, on top of the non-privileged ones the
+    /// event handlers need.
+    ///
+    /// Privileged intents are only requested when set here, as Discord
+    /// refuses the connection if they aren't enabled in the developer portal.
     #[must_use]
     pub const fn intents(mut self, intents: Intents) -> Self {
         self.intents = intents;
@@ -94,9 +497,163 @@ impl ClientBuilder {
         self
     }
 
+    /// Sets the resource types to cache, every type by default.
+    ///
+    /// Only the resources received through the requested intents are cached.
+    /// The events updating them are received even if no event handler uses
+    /// them.
+    #[must_use]
+    pub const fn cache_resource_types(mut self, cache_resource_types: ResourceType) -> Self {
+        self.cache_resource_types = cache_resource_types;
+
+        self
+    }
+
+    /// Sets the number of messages to cache per channel.
+    #[must_use]
+    pub const fn message_cache_size(mut self, message_cache_size: usize) -> Self {
+        self.message_cache_size = Some(message_cache_size);
+
+        self
+    }
+
+    /// Caches nothing, leaving [`Context::cache`] empty and receiving no
+    /// events on the cache's behalf.
+    #[must_use]
+    pub const fn disable_cache(mut self) -> Self {
+        self.cache_enabled = false;
+
+        self
+    }
+
+    /// Sets the cache to update from events and hand to the handlers, instead
+    /// of an [`InMemoryCache`] built from the other cache settings.
+    ///
+    /// This can be a custom [`Cache`] implementation, or an [`InMemoryCache`]
+    /// configured beyond the other cache settings or shared with other code.
+    /// The handlers retrieve it by its type with [`Context::cache`].
+    #[must_use]
+    pub fn cache<C: Cache>(mut self, cache: Arc<C>) -> Self {
+        self.cache = Some(SharedCache::new(cache));
+        self.cache_enabled = true;
+
+        self
+    }
+
+    /// Shares a value with the handlers, retrieved by its type with
+    /// [`Context::data`], such as a database pool.
+    ///
+    /// Adding a second value of the same type replaces the first.
+    #[must_use]
+    pub fn data<T: Send + Sync + 'static>(mut self, value: T) -> Self {
+        self.data.insert(value);
+
+        self
+    }
+
+    /// Runs the shards numbered within `numbers` out of `total`, instead of
+    /// all of the shards Discord recommends, to split a bot across processes.
+    #[must_use]
+    pub const fn shards(mut self, numbers: Range<u32>, total: u32) -> Self {
+        self.shard_range = Some(ShardRange { numbers, total });
+
+        self
+    }
+
+    /// Adds an event handler, which receives every event after the ones added
+    /// before it.
     #[must_use]
     pub fn event_handler(mut self, event_handler: impl EventHandler + 'static) -> Self {
-        self.event_handler = Some(Box::new(event_handler));
+        self.event_handlers.push(Box::new(event_handler));
+
+        self
+    }
+
+    /// Adds a middleware wrapping the dispatch of events to the event
+    /// handlers, inside the ones added before it.
+    #[must_use]
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middlewares.push(Box::new(middleware));
+
+        self
+    }
+
+    /// Sets how many events are dispatched to the event handlers at once,
+    /// [`MAX_CONCURRENT_EVENTS`] by default.
+    ///
+    /// Once the limit is reached, further events wait for a dispatch to
+    /// finish, without holding up the gateway. Events are dropped with a
+    /// warning if too many are waiting, such as when handlers are stuck.
+    ///
+    /// # Panics
+    ///
+    /// Panics on [`ClientBuilder::try_build`] if the limit exceeds
+    /// [`Semaphore::MAX_PERMITS`](tokio::sync::Semaphore::MAX_PERMITS).
+    #[must_use]
+    pub const fn max_concurrent_events(mut self, max_concurrent_events: NonZeroUsize) -> Self {
+        self.max_concurrent_events = max_concurrent_events;
+
+        self
+    }
+
+    /// Sets whether the events of a guild are dispatched one at a time, in
+    /// the order they're received, rather than concurrently.
+    ///
+    /// Events outside of a guild are always dispatched concurrently. A
+    /// guild's events are dropped with a warning if too many are queued
+    /// behind a slow handler, leaving the other guilds unaffected.
+    #[must_use]
+    pub const fn ordered_guild_events(mut self, ordered_guild_events: bool) -> Self {
+        self.ordered_guild_events = ordered_guild_events;
+
+        self
+    }
+
+    /// Sets how many times in a row a shard may fail to reconnect before
+    /// [`Client::start`] gives up with an error, retrying forever by default.
+    #[must_use]
+    pub const fn max_reconnect_attempts(mut self, max_reconnect_attempts: u8) -> Self {
+        self.max_reconnect_attempts = Some(max_reconnect_attempts);
+
+        self
+    }
+
+    /// Sets how long shutting down waits for in-flight handlers before
+    /// running the shutdown hooks anyway, [`SHUTDOWN_TIMEOUT`] by default.
+    #[must_use]
+    pub const fn shutdown_timeout(mut self, shutdown_timeout: Duration) -> Self {
+        self.shutdown_timeout = shutdown_timeout;
+
+        self
+    }
+
+    /// Sets the store shards' sessions are saved to on shutdown and resumed
+    /// from on startup, so events sent while restarting aren't missed.
+    #[must_use]
+    pub fn session_store(mut self, session_store: impl SessionStore + 'static) -> Self {
+        self.session_store = Some(Box::new(session_store));
+
+        self
+    }
+
+    /// Sets the command registry application command interactions are
+    /// dispatched to.
+    ///
+    /// Interaction events are still passed to the event handlers afterwards.
+    #[must_use]
+    pub fn command_registry(mut self, command_registry: CommandRegistry) -> Self {
+        self.command_registry = Some(command_registry);
+
+        self
+    }
+
+    /// Sets the component registry message component and modal submit
+    /// interactions are dispatched to.
+    ///
+    /// Interaction events are still passed to the event handlers afterwards.
+    #[must_use]
+    pub fn component_registry(mut self, component_registry: ComponentRegistry) -> Self {
+        self.component_registry = Some(component_registry);
 
         self
     }
@@ -106,6 +663,127 @@ impl ClientBuilder {
 pub enum ClientBuilderError {
     #[error("HTTP client doesn't have a token set")]
     MissingHTTPClientToken,
-    #[error("Event handler is not set")]
+    #[error("No event handler is set")]
     MissingEventHandler,
+    #[error("Shard range {numbers:?} is empty or exceeds the total of {total} shards")]
+    InvalidShardRange { numbers: Range<u32>, total: u32 },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("Failed to fetch the recommended shard count: {0}")]
+    RecommendedShards(#[from] StartRecommendedError),
+    #[error("Shard {shard_id} was fatally closed with close code {close_code:?}")]
+    FatallyClosed {
+        shard_id: ShardId,
+        close_code: Option<u16>,
+    },
+    #[error("Shard {shard_id} failed to reconnect: {source}")]
+    Reconnect {
+        shard_id: ShardId,
+        source: ReceiveMessageError,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use secrecy::SecretString;
+    use twilight_cache_inmemory::{InMemoryCache, ResourceType};
+    use twilight_gateway::{Event, EventTypeFlags, ShardId};
+
+    use crate::{
+        cache::Cache,
+        client::{Client, ClientBuilder, ClientBuilderError, SHARD_STATUS_EVENT_FLAGS},
+        event_handler::EventHandler,
+    };
+
+    /// Handles no events, leaving the event flags to the client.
+    struct NoEvents;
+
+    impl EventHandler for NoEvents {
+        fn used_event_flags(&self) -> EventTypeFlags {
+            EventTypeFlags::empty()
+        }
+    }
+
+    /// Counts nothing, but needs the events messages are counted from.
+    struct MessageCount;
+
+    impl Cache for MessageCount {
+        fn update(&self, _event: &Event) {}
+
+        fn event_flags(&self) -> EventTypeFlags {
+            EventTypeFlags::MESSAGE_CREATE
+        }
+    }
+
+    fn builder() -> ClientBuilder {
+        Client::builder(&SecretString::from("token")).event_handler(NoEvents)
+    }
+
+    #[tokio::test]
+    async fn test_invalid_shard_range() {
+        assert!(matches!(
+            builder().shards(3..3, 4).try_build(),
+            Err(ClientBuilderError::InvalidShardRange { .. })
+        ));
+        assert!(matches!(
+            builder().shards(0..5, 4).try_build(),
+            Err(ClientBuilderError::InvalidShardRange { .. })
+        ));
+        assert!(builder().shards(2..4, 4).try_build().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_cache_event_flags() {
+        let client = builder()
+            .cache_resource_types(ResourceType::ROLE)
+            .try_build()
+            .unwrap();
+
+        assert!(
+            client
+                .event_loop
+                .event_flags
+                .contains(EventTypeFlags::ROLE_CREATE)
+        );
+        assert!(
+            !client
+                .event_loop
+                .event_flags
+                .contains(EventTypeFlags::MESSAGE_CREATE)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_disable_cache() {
+        let client = builder().disable_cache().try_build().unwrap();
+
+        assert!(client.event_loop.cache.is_none());
+        assert_eq!(
+            client.event_loop.event_flags,
+            EventTypeFlags::INTERACTION_CREATE | SHARD_STATUS_EVENT_FLAGS
+        );
+    }
+
+    #[tokio::test]
+    async fn test_custom_cache() {
+        let client = builder()
+            .disable_cache()
+            .cache(Arc::new(MessageCount))
+            .try_build()
+            .unwrap();
+        let context = client.event_loop.create_context(ShardId::ONE);
+
+        assert!(context.cache::<MessageCount>().is_some());
+        assert!(context.cache::<InMemoryCache>().is_none());
+        assert!(
+            client
+                .event_loop
+                .event_flags
+                .contains(EventTypeFlags::MESSAGE_CREATE)
+        );
+    }
 }
@@ -0,0 +1,180 @@
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use core::{
+    pin::Pin,
+    task::{Context as TaskContext, Poll},
+    time::Duration,
+};
+
+use futures_core::Stream;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use twilight_model::application::interaction::Interaction;
+
+type Filter = Box<dyn Fn(&Interaction) -> bool + Send + Sync>;
+
+/// The collectors waiting for interactions, fed from the client's event loop.
+///
+/// An interaction a collector matches is handed to it instead of the command
+/// and component registries, while still reaching the event handlers.
+#[derive(Default)]
+pub struct Collectors {
+    collectors: Mutex<Vec<CollectorSender>>,
+}
+
+struct CollectorSender {
+    filter: Filter,
+    sender: UnboundedSender<Interaction>,
+}
+
+impl Collectors {
+    /// Starts collecting the interactions `filter` matches, until the returned
+    /// collector is dropped.
+    pub fn collect(
+        &self,
+        filter: impl Fn(&Interaction) -> bool + Send + Sync + 'static,
+    ) -> InteractionCollector {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        self.lock_collectors().push(CollectorSender {
+            filter: Box::new(filter),
+            sender,
+        });
+
+        InteractionCollector { receiver }
+    }
+
+    /// Hands an interaction to the first collector matching it, returning
+    /// whether one did.
+    pub fn dispatch(&self, interaction: &Interaction) -> bool {
+        let mut collectors = self.lock_collectors();
+        collectors.retain(|collector| !collector.sender.is_closed());
+
+        collectors.iter().any(|collector| {
+            (collector.filter)(interaction) && collector.sender.send(interaction.clone()).is_ok()
+        })
+    }
+
+    fn lock_collectors(&self) -> MutexGuard<'_, Vec<CollectorSender>> {
+        self.collectors
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl core::fmt::Debug for Collectors {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Collectors")
+            .field("collectors", &self.lock_collectors().len())
+            .finish()
+    }
+}
+
+/// A stream of the interactions matching a collector's filter.
+#[derive(Debug)]
+pub struct InteractionCollector {
+    receiver: UnboundedReceiver<Interaction>,
+}
+
+impl InteractionCollector {
+    /// Waits for the next interaction.
+    pub async fn next_interaction(&mut self) -> Option<Interaction> {
+        self.receiver.recv().await
+    }
+
+    /// Waits for the next interaction, returning `None` once `timeout` has
+    /// passed.
+    pub async fn next_interaction_timeout(&mut self, timeout: Duration) -> Option<Interaction> {
+        tokio::time::timeout(timeout, self.next_interaction())
+            .await
+            .ok()
+            .flatten()
+    }
+}
+
+impl Stream for InteractionCollector {
+    type Item = Interaction;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use twilight_model::{
+        application::interaction::{Interaction, InteractionType},
+        id::Id,
+        oauth::ApplicationIntegrationMap,
+    };
+
+    use crate::collector::Collectors;
+
+    #[allow(deprecated)]
+    fn interaction(id: u64) -> Interaction {
+        Interaction {
+            app_permissions: None,
+            application_id: Id::new(1),
+            authorizing_integration_owners: ApplicationIntegrationMap {
+                guild: None,
+                user: None,
+            },
+            channel: None,
+            channel_id: None,
+            context: None,
+            data: None,
+            entitlements: Vec::new(),
+            guild: None,
+            guild_id: None,
+            guild_locale: None,
+            id: Id::new(id),
+            kind: InteractionType::MessageComponent,
+            locale: None,
+            member: None,
+            message: None,
+            token: String::new(),
+            user: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_collect_matching_interactions() {
+        let collectors = Collectors::default();
+        let mut collector = collectors.collect(|interaction| interaction.id == Id::new(1));
+
+        assert!(!collectors.dispatch(&interaction(2)));
+        assert!(collectors.dispatch(&interaction(1)));
+        assert_eq!(
+            collector
+                .next_interaction()
+                .await
+                .map(|interaction| interaction.id),
+            Some(Id::new(1))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_first_matching_collector_wins() {
+        let collectors = Collectors::default();
+        let mut first = collectors.collect(|_| true);
+        let mut second = collectors.collect(|_| true);
+
+        assert!(collectors.dispatch(&interaction(1)));
+        assert!(first.next_interaction().await.is_some());
+        assert!(
+            second
+                .next_interaction_timeout(Duration::from_millis(10))
+                .await
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_dropped_collectors_are_removed() {
+        let collectors = Collectors::default();
+        drop(collectors.collect(|_| true));
+
+        assert!(!collectors.dispatch(&interaction(1)));
+        assert!(collectors.lock_collectors().is_empty());
+    }
+}
@@ -1,33 +1,171 @@
-use twilight_model::application::{
-    command::{Command as TwilightCommand, CommandOptionType, CommandType},
-    interaction::{
-        Interaction,
-        application_command::{CommandDataOption, CommandOptionValue},
+use std::collections::HashMap;
+
+use twilight_model::{
+    application::{
+        command::{
+            Command as TwilightCommand, CommandOption, CommandOptionChoice, CommandOptionType,
+            CommandType,
+        },
+        interaction::{
+            Interaction, InteractionDataResolved,
+            application_command::{
+                CommandData as ApplicationCommandData, CommandDataOption, CommandOptionValue,
+            },
+        },
     },
+    channel::ChannelType,
+    id::{Id, marker::ChannelMarker},
 };
-use twilight_util::builder::command::CommandBuilder;
+use twilight_util::builder::command::{CommandBuilder, SubCommandBuilder, SubCommandGroupBuilder};
 
 use crate::{Context, exts::interaction::InteractionExtError};
 
+pub mod registry;
+
+pub use registry::CommandRegistry;
+
+#[async_trait::async_trait]
 pub trait CommandData {
     const COMMAND_NAME: &'static str;
     const COMMAND_DESCRIPTION: &'static str;
+    /// Whether the command is a slash command or a user or message context
+    /// menu command.
+    const COMMAND_TYPE: CommandType = CommandType::ChatInput;
+    /// Whether any of the command's options, or of its subcommands' options,
+    /// are marked with `#[option(autocomplete)]`.
+    const HAS_AUTOCOMPLETE: bool = false;
+    /// Whether the response is deferred as ephemeral when the command runs
+    /// for too long, or `None` to follow the registry's setting.
+    const DEFER_EPHEMERAL: Option<bool> = None;
 
     fn command() -> TwilightCommand;
+
     fn command_builder() -> CommandBuilder {
         CommandBuilder::new(
             Self::COMMAND_NAME,
             Self::COMMAND_DESCRIPTION,
-            CommandType::ChatInput,
+            Self::COMMAND_TYPE,
         )
     }
+
+    /// Returns whether the response is deferred as ephemeral, preferring the
+    /// setting of the subcommand the options run.
+    fn defer_ephemeral(_options: &[CommandDataOption]) -> Option<bool> {
+        Self::DEFER_EPHEMERAL
+    }
+
+    /// Returns the choices to suggest for the focused option, from the
+    /// `Autocomplete` implementation of the command or subcommand it belongs
+    /// to.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CommandError::MissingAutocomplete`] error if the command
+    /// doesn't support autocomplete.
+    async fn dispatch_autocomplete(
+        _context: &Context,
+        _interaction: &Interaction,
+        _options: &[CommandDataOption],
+        _resolved: Option<&InteractionDataResolved>,
+    ) -> Result<Vec<CommandOptionChoice>, CommandError> {
+        Err(CommandError::MissingAutocomplete(
+            Self::COMMAND_NAME.to_string(),
+        ))
+    }
+}
+
+/// A command that can be nested in another, as a subcommand or as a
+/// subcommand group.
+///
+/// Implemented by the `Command` derive on slash commands, as context menu
+/// commands can't be nested.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be a subcommand",
+    note = "only slash commands can be subcommands, not context menu commands"
+)]
+pub trait NestedCommand: CommandData {
+    /// Builds the command as an option of a parent command.
+    fn command_option() -> CommandOption;
+}
+
+/// A slash command that can be a subcommand, on its own or in a subcommand
+/// group.
+///
+/// Implemented by the `Command` derive on structs.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be a subcommand of a subcommand group",
+    note = "subcommand groups can only contain subcommands, not other subcommand groups"
+)]
+pub trait SubCommand: CommandData {
+    fn subcommand() -> SubCommandBuilder;
+}
+
+/// A slash command whose variants are its subcommands.
+///
+/// Implemented by the `Command` derive on enums. It can be nested in another
+/// command as a subcommand group as long as none of its variants are groups
+/// themselves.
+pub trait SubCommandGroup: CommandData {
+    /// The commands wrapped by the variants, as a tuple.
+    type SubCommands;
+
+    /// Builds the group, without its subcommands.
+    fn subcommand_group() -> SubCommandGroupBuilder;
+}
+
+impl<T> NestedCommand for T
+where
+    T: SubCommandGroup,
+    T::SubCommands: SubCommands,
+{
+    fn command_option() -> CommandOption {
+        T::subcommand_group()
+            .subcommands(T::SubCommands::subcommands())
+            .build()
+    }
+}
+
+/// A tuple of the subcommands of a subcommand group.
+pub trait SubCommands {
+    fn subcommands() -> Vec<SubCommandBuilder>;
 }
 
+macro_rules! impl_subcommands {
+    () => {};
+    ($first:ident $(, $rest:ident)*) => {
+        impl<$first: SubCommand, $($rest: SubCommand),*> SubCommands for ($first, $($rest,)*) {
+            fn subcommands() -> Vec<SubCommandBuilder> {
+                vec![$first::subcommand(), $($rest::subcommand()),*]
+            }
+        }
+
+        impl_subcommands!($($rest),*);
+    };
+}
+
+// Discord allows up to 25 subcommands in a group.
+impl_subcommands!(
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y
+);
+
 pub trait CommandOptions: Sized {
     /// # Errors
     ///
     /// Returns a `CommandOptionsError` if the options could not be parsed.
-    fn parse_options(options: &[CommandDataOption]) -> Result<Self, CommandOptionsError>;
+    fn parse_options(
+        options: &[CommandDataOption],
+        resolved: Option<&InteractionDataResolved>,
+    ) -> Result<Self, CommandOptionsError>;
+
+    /// Parses the command from an interaction's data, including the target of
+    /// context menu commands.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `CommandOptionsError` if the options or target could not be parsed.
+    fn parse_command_data(data: &ApplicationCommandData) -> Result<Self, CommandOptionsError> {
+        Self::parse_options(&data.options, data.resolved.as_ref())
+    }
 }
 
 #[async_trait::async_trait]
@@ -39,6 +177,126 @@ pub trait Command: CommandData {
     ) -> Result<(), CommandExecuteError>;
 }
 
+/// The option a user is currently typing in, as sent with autocomplete
+/// interactions.
+#[derive(Debug, Clone)]
+pub struct FocusedOption {
+    pub name: String,
+    pub value: String,
+    pub kind: CommandOptionType,
+}
+
+pub trait AutocompleteOptions: Sized {
+    /// The command's options, any of which may not have been filled in yet.
+    type Partial: Send;
+
+    /// # Errors
+    ///
+    /// Returns a `CommandOptionsError` if no option is focused, or if the
+    /// subcommand could not be found.
+    fn parse_partial_options(
+        options: &[CommandDataOption],
+        resolved: Option<&InteractionDataResolved>,
+    ) -> Result<(Self::Partial, FocusedOption), CommandOptionsError>;
+}
+
+/// Suggests choices for the options marked with `#[option(autocomplete)]`.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has options with autocomplete but doesn't implement `Autocomplete`"
+)]
+#[async_trait::async_trait]
+pub trait Autocomplete: AutocompleteOptions {
+    /// Returns the choices to suggest for the focused option.
+    async fn autocomplete(
+        context: &Context,
+        interaction: &Interaction,
+        options: Self::Partial,
+        focused: FocusedOption,
+    ) -> Result<Vec<CommandOptionChoice>, CommandExecuteError>;
+}
+
+/// A fixed set of values an option can take.
+///
+/// Usually derived with `bouncer_macros::CommandChoice` on a fieldless enum.
+pub trait CommandChoice: Sized {
+    /// The type of the option the choices are offered on.
+    const OPTION_TYPE: CommandOptionType;
+
+    fn choices() -> Vec<CommandOptionChoice>;
+
+    /// # Errors
+    ///
+    /// Returns a `CommandOptionsError` if the value doesn't match any choice.
+    fn from_option_value(
+        name: &str,
+        value: &CommandOptionValue,
+    ) -> Result<Self, CommandOptionsError>;
+}
+
+/// A set of commands that can be registered, looked up by name and
+/// dispatched as one.
+///
+/// Usually derived with `bouncer_macros::Commands` on an enum whose variants
+/// each wrap a single command.
+#[async_trait::async_trait]
+pub trait Commands: Sized {
+    /// Returns every command in the set, ready to be registered.
+    fn all_commands() -> Vec<TwilightCommand>;
+
+    /// # Errors
+    ///
+    /// Returns a [`CommandError::UnknownCommand`] error if no command matches the data's
+    /// name and type, or a [`CommandError::CommandOptionsError`] error if the options
+    /// could not be parsed.
+    fn parse_from_command_data(data: &ApplicationCommandData) -> Result<Self, CommandError>;
+
+    /// Returns whether the response to the command the data refers to is
+    /// deferred as ephemeral, or `None` to follow the registry's setting.
+    fn defer_ephemeral(_data: &ApplicationCommandData) -> Option<bool> {
+        None
+    }
+
+    async fn execute(
+        &self,
+        context: &Context,
+        interaction: &Interaction,
+    ) -> Result<(), CommandExecuteError>;
+
+    /// Returns the choices to suggest for the focused option of the command
+    /// named `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CommandError::MissingAutocomplete`] error if no command named
+    /// `name` has options with autocomplete.
+    async fn autocomplete(
+        context: &Context,
+        interaction: &Interaction,
+        name: &str,
+        options: &[CommandDataOption],
+        resolved: Option<&InteractionDataResolved>,
+    ) -> Result<Vec<CommandOptionChoice>, CommandError>;
+}
+
+/// # Errors
+///
+/// Returns a `CommandOptionsError` if no option is focused.
+pub fn parse_focused_option(
+    options: &[CommandDataOption],
+) -> Result<FocusedOption, CommandOptionsError> {
+    options
+        .iter()
+        .find_map(|opt| match &opt.value {
+            CommandOptionValue::Focused(value, kind) => Some(FocusedOption {
+                name: opt.name.clone(),
+                value: value.clone(),
+                kind: *kind,
+            }),
+            _ => None,
+        })
+        .ok_or(CommandOptionsError::MissingFocusedOption)
+}
+
 /// # Errors
 ///
 /// Returns a `CommandOptionsError` if the options could not be parsed.
@@ -75,8 +333,184 @@ pub fn parse_required_option<T>(
     )
 }
 
+/// # Errors
+///
+/// Returns a `CommandOptionsError` if the options could not be parsed.
+pub fn parse_optional_choice<T: CommandChoice>(
+    options: &[CommandDataOption],
+    name: &str,
+) -> Result<Option<T>, CommandOptionsError> {
+    options
+        .iter()
+        .find(|opt| opt.name == name)
+        .map(|opt| T::from_option_value(name, &opt.value))
+        .transpose()
+}
+
+/// # Errors
+///
+/// Returns a `CommandOptionsError` if the options could not be parsed.
+pub fn parse_required_choice<T: CommandChoice>(
+    options: &[CommandDataOption],
+    name: &str,
+) -> Result<T, CommandOptionsError> {
+    options.iter().find(|opt| opt.name == name).map_or_else(
+        || Err(CommandOptionsError::MissingRequiredOption(name.to_string())),
+        |opt| T::from_option_value(name, &opt.value),
+    )
+}
+
+/// Builds an option offering the choices of `T`.
+#[must_use]
+pub fn choice_option<T: CommandChoice>(
+    name: &str,
+    description: &str,
+    required: bool,
+    name_localizations: &[(&str, &str)],
+    description_localizations: &[(&str, &str)],
+) -> CommandOption {
+    CommandOption {
+        autocomplete: None,
+        channel_types: None,
+        choices: Some(T::choices()),
+        description: description.to_string(),
+        description_localizations: localizations(description_localizations),
+        kind: T::OPTION_TYPE,
+        max_length: None,
+        max_value: None,
+        min_length: None,
+        min_value: None,
+        name: name.to_string(),
+        name_localizations: localizations(name_localizations),
+        options: None,
+        required: Some(required),
+    }
+}
+
+/// Collects `(locale, value)` pairs into the localizations of a command, an
+/// option or a choice, or `None` if there are none.
+#[must_use]
+pub fn localizations(localizations: &[(&str, &str)]) -> Option<HashMap<String, String>> {
+    if localizations.is_empty() {
+        return None;
+    }
+
+    Some(
+        localizations
+            .iter()
+            .map(|&(locale, value)| (locale.to_string(), value.to_string()))
+            .collect(),
+    )
+}
+
+/// # Errors
+///
+/// Returns a `CommandOptionsError` if the option's value is missing from the resolved data.
+pub fn resolve_option<T>(
+    resolved: Option<&InteractionDataResolved>,
+    name: &str,
+    resolver: impl FnOnce(&InteractionDataResolved) -> Option<T>,
+) -> Result<T, CommandOptionsError> {
+    resolved
+        .and_then(resolver)
+        .ok_or_else(|| CommandOptionsError::UnresolvedOption(name.to_string()))
+}
+
+/// # Errors
+///
+/// Returns a `CommandOptionsError` if the interaction has no target.
+pub fn parse_target_id<T>(data: &ApplicationCommandData) -> Result<Id<T>, CommandOptionsError> {
+    data.target_id
+        .map(Id::cast)
+        .ok_or(CommandOptionsError::MissingTarget)
+}
+
+/// # Errors
+///
+/// Returns a `CommandOptionsError` if the interaction has no target, or if the target
+/// is missing from the resolved data.
+pub fn resolve_target<T, U>(
+    data: &ApplicationCommandData,
+    resolver: impl FnOnce(&InteractionDataResolved, Id<T>) -> Option<U>,
+) -> Result<U, CommandOptionsError> {
+    let target_id = parse_target_id(data)?;
+
+    data.resolved
+        .as_ref()
+        .and_then(|resolved| resolver(resolved, target_id))
+        .ok_or(CommandOptionsError::UnresolvedTarget)
+}
+
+/// # Errors
+///
+/// Returns a `CommandOptionsError` if the value is outside of the option's range.
+pub fn validate_option_value<T: PartialOrd>(
+    name: &str,
+    value: &T,
+    min_value: Option<T>,
+    max_value: Option<T>,
+) -> Result<(), CommandOptionsError> {
+    if min_value.is_some_and(|min_value| *value < min_value)
+        || max_value.is_some_and(|max_value| *value > max_value)
+    {
+        return Err(CommandOptionsError::ValueOutOfRange(name.to_string()));
+    }
+
+    Ok(())
+}
+
+/// # Errors
+///
+/// Returns a `CommandOptionsError` if the value's length is outside of the option's range.
+pub fn validate_option_length(
+    name: &str,
+    value: &str,
+    min_length: Option<u16>,
+    max_length: Option<u16>,
+) -> Result<(), CommandOptionsError> {
+    let length = value.chars().count();
+    if min_length.is_some_and(|min_length| length < usize::from(min_length))
+        || max_length.is_some_and(|max_length| length > usize::from(max_length))
+    {
+        return Err(CommandOptionsError::LengthOutOfRange(name.to_string()));
+    }
+
+    Ok(())
+}
+
+/// # Errors
+///
+/// Returns a `CommandOptionsError` if the channel is missing from the resolved data or
+/// is not one of the option's channel types.
+pub fn validate_option_channel_type(
+    name: &str,
+    channel_id: Id<ChannelMarker>,
+    resolved: Option<&InteractionDataResolved>,
+    channel_types: &[ChannelType],
+) -> Result<(), CommandOptionsError> {
+    let kind = resolve_option(resolved, name, |resolved| {
+        resolved
+            .channels
+            .get(&channel_id)
+            .map(|channel| channel.kind)
+    })?;
+
+    if !channel_types.contains(&kind) {
+        return Err(CommandOptionsError::UnexpectedChannelType(
+            name.to_string(),
+            kind,
+        ));
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum CommandError {
+    #[error("Could not find command {0}")]
+    UnknownCommand(String),
+    #[error("Command {0} does not support autocomplete")]
+    MissingAutocomplete(String),
     #[error(transparent)]
     CommandExecuteError(#[from] CommandExecuteError),
     #[error(transparent)]
@@ -99,4 +533,218 @@ pub enum CommandOptionsError {
     UnexpectedOptionType(String, CommandOptionType),
     #[error("Missing required option {0}")]
     MissingRequiredOption(String),
+    #[error("Missing resolved data for option {0}")]
+    UnresolvedOption(String),
+    #[error("Missing subcommand")]
+    MissingSubCommand,
+    #[error("Unknown subcommand {0}")]
+    UnknownSubCommand(String),
+    #[error("Unknown choice for {0}: received {1}")]
+    UnknownChoice(String, String),
+    #[error("Value of {0} is out of range")]
+    ValueOutOfRange(String),
+    #[error("Length of {0} is out of range")]
+    LengthOutOfRange(String),
+    #[error("Unexpected channel type for {0}: received {1:?}")]
+    UnexpectedChannelType(String, ChannelType),
+    #[error("Missing focused option")]
+    MissingFocusedOption,
+    #[error("Missing target")]
+    MissingTarget,
+    #[error("Missing resolved data for target")]
+    UnresolvedTarget,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use twilight_model::{
+        application::{
+            command::{CommandOptionChoice, CommandOptionType},
+            interaction::{
+                InteractionChannel, InteractionDataResolved,
+                application_command::{CommandDataOption, CommandOptionValue},
+            },
+        },
+        channel::ChannelType,
+        guild::Permissions,
+        id::Id,
+    };
+
+    use crate::command::{
+        CommandChoice, CommandOptionsError, parse_optional_choice, parse_required_choice,
+        validate_option_channel_type, validate_option_length, validate_option_value,
+    };
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum Unit {
+        Seconds,
+        Minutes,
+    }
+
+    impl CommandChoice for Unit {
+        const OPTION_TYPE: CommandOptionType = CommandOptionType::String;
+
+        fn choices() -> Vec<CommandOptionChoice> {
+            Vec::new()
+        }
+
+        fn from_option_value(
+            name: &str,
+            value: &CommandOptionValue,
+        ) -> Result<Self, CommandOptionsError> {
+            match value {
+                CommandOptionValue::String(value) if value == "s" => Ok(Self::Seconds),
+                CommandOptionValue::String(value) if value == "m" => Ok(Self::Minutes),
+                CommandOptionValue::String(value) => Err(CommandOptionsError::UnknownChoice(
+                    name.to_string(),
+                    value.clone(),
+                )),
+                _ => Err(CommandOptionsError::UnexpectedOptionType(
+                    name.to_string(),
+                    value.kind(),
+                )),
+            }
+        }
+    }
+
+    fn option(name: &str, value: CommandOptionValue) -> CommandDataOption {
+        CommandDataOption {
+            name: name.to_string(),
+            value,
+        }
+    }
+
+    fn resolved_channel(kind: ChannelType) -> InteractionDataResolved {
+        let channel = InteractionChannel {
+            id: Id::new(1),
+            kind,
+            name: String::from("general"),
+            parent_id: None,
+            permissions: Permissions::empty(),
+            thread_metadata: None,
+        };
+
+        InteractionDataResolved {
+            attachments: HashMap::new(),
+            channels: HashMap::from([(channel.id, channel)]),
+            members: HashMap::new(),
+            messages: HashMap::new(),
+            roles: HashMap::new(),
+            users: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_required_choice() {
+        let options = [option(
+            "unit",
+            CommandOptionValue::String(String::from("m")),
+        )];
+        assert_eq!(
+            parse_required_choice::<Unit>(&options, "unit").unwrap(),
+            Unit::Minutes
+        );
+
+        assert!(matches!(
+            parse_required_choice::<Unit>(&[], "unit"),
+            Err(CommandOptionsError::MissingRequiredOption(name)) if name == "unit"
+        ));
+    }
+
+    #[test]
+    fn test_parse_unknown_choice() {
+        let options = [option(
+            "unit",
+            CommandOptionValue::String(String::from("h")),
+        )];
+        assert!(matches!(
+            parse_required_choice::<Unit>(&options, "unit"),
+            Err(CommandOptionsError::UnknownChoice(name, value)) if name == "unit" && value == "h"
+        ));
+    }
+
+    #[test]
+    fn test_parse_optional_choice() {
+        let options = [option(
+            "unit",
+            CommandOptionValue::String(String::from("s")),
+        )];
+        assert_eq!(
+            parse_optional_choice::<Unit>(&options, "unit").unwrap(),
+            Some(Unit::Seconds)
+        );
+        assert_eq!(parse_optional_choice::<Unit>(&[], "unit").unwrap(), None);
+
+        let options = [option("unit", CommandOptionValue::Integer(1))];
+        assert!(matches!(
+            parse_optional_choice::<Unit>(&options, "unit"),
+            Err(CommandOptionsError::UnexpectedOptionType(
+                _,
+                CommandOptionType::Integer
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_validate_option_value() {
+        assert!(validate_option_value("limit", &5, Some(1), Some(10)).is_ok());
+        assert!(validate_option_value("limit", &1, Some(1), Some(10)).is_ok());
+        assert!(validate_option_value("limit", &-3.5, None, Some(0.0)).is_ok());
+
+        assert!(matches!(
+            validate_option_value("limit", &0, Some(1), None),
+            Err(CommandOptionsError::ValueOutOfRange(name)) if name == "limit"
+        ));
+        assert!(matches!(
+            validate_option_value("limit", &10.5, None, Some(10.0)),
+            Err(CommandOptionsError::ValueOutOfRange(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_option_length() {
+        assert!(validate_option_length("reason", "spam", Some(1), Some(4)).is_ok());
+        // Lengths are counted in characters, not bytes.
+        assert!(validate_option_length("reason", "éé", None, Some(2)).is_ok());
+
+        assert!(matches!(
+            validate_option_length("reason", "", Some(1), None),
+            Err(CommandOptionsError::LengthOutOfRange(name)) if name == "reason"
+        ));
+        assert!(matches!(
+            validate_option_length("reason", "spam", None, Some(3)),
+            Err(CommandOptionsError::LengthOutOfRange(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_option_channel_type() {
+        let resolved = resolved_channel(ChannelType::GuildText);
+        assert!(
+            validate_option_channel_type(
+                "channel",
+                Id::new(1),
+                Some(&resolved),
+                &[ChannelType::GuildText, ChannelType::GuildForum],
+            )
+            .is_ok()
+        );
+
+        assert!(matches!(
+            validate_option_channel_type(
+                "channel",
+                Id::new(1),
+                Some(&resolved),
+                &[ChannelType::GuildVoice],
+            ),
+            Err(CommandOptionsError::UnexpectedChannelType(name, ChannelType::GuildText))
+                if name == "channel"
+        ));
+        assert!(matches!(
+            validate_option_channel_type("channel", Id::new(1), None, &[ChannelType::GuildText]),
+            Err(CommandOptionsError::UnresolvedOption(_))
+        ));
+    }
 }
@@ -0,0 +1,547 @@
+use std::{collections::HashMap, sync::Arc};
+
+use core::{future::Future, pin::Pin, time::Duration};
+
+use twilight_model::application::{
+    command::{Command as TwilightCommand, CommandOption, CommandOptionChoice, CommandType},
+    interaction::{
+        Interaction, InteractionData, InteractionType,
+        application_command::CommandData as ApplicationCommandData,
+    },
+};
+
+use crate::{
+    Context,
+    command::{Command, CommandData, CommandError, CommandExecuteError, CommandOptions, Commands},
+    exts::interaction::InteractionExt as _,
+};
+
+pub(crate) type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+type CommandHandler = for<'a> fn(
+    &'a Context,
+    &'a Interaction,
+    &'a ApplicationCommandData,
+) -> BoxFuture<'a, Result<(), CommandError>>;
+
+/// Returns whether a command's response is deferred as ephemeral, overriding
+/// the registry's setting.
+type DeferEphemeral = fn(&ApplicationCommandData) -> Option<bool>;
+
+type AutocompleteHandler =
+    for<'a> fn(
+        &'a Context,
+        &'a Interaction,
+        &'a ApplicationCommandData,
+    ) -> BoxFuture<'a, Result<Vec<CommandOptionChoice>, CommandError>>;
+
+type ErrorHandler =
+    Arc<dyn Fn(Context, Interaction, CommandError) -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// How long a command may run before its response is deferred by default,
+/// leaving time for the deferral to reach Discord within the three-second
+/// deadline.
+const AUTO_DEFER_THRESHOLD: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy)]
+struct RegisteredCommand {
+    execute: CommandHandler,
+    defer_ephemeral: DeferEphemeral,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct AutoDefer {
+    threshold: Duration,
+    ephemeral: bool,
+}
+
+/// Looks up application commands by name, parses their options and executes
+/// them.
+///
+/// A registry is handed to [`ClientBuilder::command_registry`], after which
+/// the client handles application command and autocomplete interactions on
+/// its own.
+///
+/// [`ClientBuilder::command_registry`]: crate::client::ClientBuilder::command_registry
+pub struct CommandRegistry {
+    handlers: HashMap<(CommandType, String), RegisteredCommand>,
+    autocomplete_handlers: HashMap<String, AutocompleteHandler>,
+    commands: Vec<TwilightCommand>,
+    error_handler: ErrorHandler,
+    auto_defer: Option<AutoDefer>,
+}
+
+impl CommandRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            handlers: HashMap::new(),
+            autocomplete_handlers: HashMap::new(),
+            commands: Vec::new(),
+            error_handler: Arc::new(log_command_error),
+            auto_defer: Some(AutoDefer {
+                threshold: AUTO_DEFER_THRESHOLD,
+                ephemeral: false,
+            }),
+        }
+    }
+
+    /// Registers a single command, along with its autocomplete if it has
+    /// options with autocomplete.
+    #[must_use]
+    pub fn command<C>(mut self) -> Self
+    where
+        C: Command + CommandOptions + Send + Sync + 'static,
+    {
+        self.insert_command(
+            C::command(),
+            RegisteredCommand {
+                execute: execute_command::<C>,
+                defer_ephemeral: defer_ephemeral_command::<C>,
+            },
+            C::HAS_AUTOCOMPLETE.then_some(autocomplete_command::<C> as AutocompleteHandler),
+        );
+
+        self
+    }
+
+    /// Registers every command of a [`Commands`] set, along with their
+    /// autocomplete.
+    #[must_use]
+    pub fn commands<C>(mut self) -> Self
+    where
+        C: Commands + Send + Sync + 'static,
+    {
+        for command in C::all_commands() {
+            let autocomplete = has_autocomplete(&command.options)
+                .then_some(autocomplete_commands::<C> as AutocompleteHandler);
+            self.insert_command(
+                command,
+                RegisteredCommand {
+                    execute: execute_commands::<C>,
+                    defer_ephemeral: C::defer_ephemeral,
+                },
+                autocomplete,
+            );
+        }
+
+        self
+    }
+
+    /// Adds a command and its autocomplete, replacing any registered before
+    /// under the same name and type, so it's only registered with Discord once.
+    fn insert_command(
+        &mut self,
+        command: TwilightCommand,
+        registered: RegisteredCommand,
+        autocomplete: Option<AutocompleteHandler>,
+    ) {
+        let key = (command.kind, command.name.clone());
+        if self.handlers.insert(key, registered).is_some() {
+            tracing::warn!(name = %command.name, "command registered twice, replacing it");
+            self.commands.retain(|registered| {
+                registered.kind != command.kind || registered.name != command.name
+            });
+        }
+
+        // Only slash commands have autocomplete, so context menu commands
+        // sharing their name leave it alone.
+        if command.kind == CommandType::ChatInput {
+            match autocomplete {
+                Some(autocomplete) => {
+                    self.autocomplete_handlers
+                        .insert(command.name.clone(), autocomplete);
+                }
+                None => {
+                    self.autocomplete_handlers.remove(&command.name);
+                }
+            }
+        }
+
+        self.commands.push(command);
+    }
+
+    /// Sets the hook called when a command fails to parse or execute.
+    ///
+    /// By default, errors are logged.
+    #[must_use]
+    pub fn on_error<F, Fut>(mut self, error_handler: F) -> Self
+    where
+        F: Fn(Context, Interaction, CommandError) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.error_handler = Arc::new(
+            move |context: Context,
+                  interaction: Interaction,
+                  error: CommandError|
+                  -> BoxFuture<'static, ()> {
+                Box::pin(error_handler(context, interaction, error))
+            },
+        );
+
+        self
+    }
+
+    /// Sets how long a command may run before its response is deferred.
+    ///
+    /// Defaults to two seconds. Replies sent after the deferral edit the
+    /// original response instead. Commands can defer as ephemeral or not on
+    /// their own with `#[command(defer_ephemeral = ...)]`.
+    #[must_use]
+    pub const fn auto_defer(mut self, threshold: Duration) -> Self {
+        self.auto_defer = Some(AutoDefer {
+            threshold,
+            ephemeral: false,
+        });
+
+        self
+    }
+
+    /// Like [`CommandRegistry::auto_defer`], with a deferred response only the
+    /// user who ran the command can see.
+    #[must_use]
+    pub const fn auto_defer_ephemeral(mut self, threshold: Duration) -> Self {
+        self.auto_defer = Some(AutoDefer {
+            threshold,
+            ephemeral: true,
+        });
+
+        self
+    }
+
+    /// Leaves deferring responses to the commands.
+    #[must_use]
+    pub const fn disable_auto_defer(mut self) -> Self {
+        self.auto_defer = None;
+
+        self
+    }
+
+    /// Returns the definitions of every registered command, ready to be
+    /// registered with Discord.
+    #[must_use]
+    pub fn all_commands(&self) -> &[TwilightCommand] {
+        &self.commands
+    }
+
+    /// Executes the command an interaction refers to, or responds with the
+    /// choices suggested for its focused option, reporting any error through
+    /// the error hook.
+    ///
+    /// Interactions that aren't application commands or autocomplete are
+    /// ignored.
+    pub async fn handle(&self, context: &Context, interaction: &Interaction) {
+        let Some(InteractionData::ApplicationCommand(data)) = interaction.data.as_ref() else {
+            return;
+        };
+
+        // Commands are tracked until the error hook has run, so its replies
+        // edit or follow up on any response the command or auto-defer sent.
+        let _tracked = (interaction.kind == InteractionType::ApplicationCommand)
+            .then(|| context.interaction_responses.track(interaction.id));
+        let result = match interaction.kind {
+            InteractionType::ApplicationCommand => {
+                self.handle_command(context, interaction, data).await
+            }
+            InteractionType::ApplicationCommandAutocomplete => {
+                self.handle_autocomplete(context, interaction, data).await
+            }
+            _ => return,
+        };
+
+        if let Err(error) = result {
+            (self.error_handler)(context.clone(), interaction.clone(), error).await;
+        }
+    }
+
+    async fn handle_command(
+        &self,
+        context: &Context,
+        interaction: &Interaction,
+        data: &ApplicationCommandData,
+    ) -> Result<(), CommandError> {
+        let registered = self
+            .handlers
+            .get(&(data.kind, data.name.clone()))
+            .ok_or_else(|| CommandError::UnknownCommand(data.name.clone()))?;
+
+        self.execute_with_auto_defer(
+            context,
+            interaction,
+            (registered.defer_ephemeral)(data),
+            (registered.execute)(context, interaction, data),
+        )
+        .await
+    }
+
+    /// Runs a command, deferring its response if it hasn't responded once the
+    /// auto-defer threshold has passed, as ephemeral if the command asks for
+    /// it or else as set on the registry.
+    async fn execute_with_auto_defer(
+        &self,
+        context: &Context,
+        interaction: &Interaction,
+        defer_ephemeral: Option<bool>,
+        mut execute: BoxFuture<'_, Result<(), CommandError>>,
+    ) -> Result<(), CommandError> {
+        let Some(auto_defer) = self.auto_defer else {
+            return execute.await;
+        };
+        let ephemeral = defer_ephemeral.unwrap_or(auto_defer.ephemeral);
+
+        match tokio::time::timeout(auto_defer.threshold, &mut execute).await {
+            Ok(result) => result,
+            Err(_elapsed) => {
+                // The command keeps running while deferring, as it may be
+                // responding at the same time.
+                let defer = async {
+                    if ephemeral {
+                        interaction.defer_ephemeral(context).await
+                    } else {
+                        interaction.defer(context).await
+                    }
+                };
+                let (result, deferred) = tokio::join!(execute, defer);
+
+                if let Err(error) = deferred {
+                    let error = CommandExecuteError::from(error).into();
+                    (self.error_handler)(context.clone(), interaction.clone(), error).await;
+                }
+
+                result
+            }
+        }
+    }
+
+    async fn handle_autocomplete(
+        &self,
+        context: &Context,
+        interaction: &Interaction,
+        data: &ApplicationCommandData,
+    ) -> Result<(), CommandError> {
+        let handler = self
+            .autocomplete_handlers
+            .get(&data.name)
+            .ok_or_else(|| CommandError::MissingAutocomplete(data.name.clone()))?;
+        let choices = handler(context, interaction, data).await?;
+
+        interaction
+            .autocomplete(context, choices)
+            .await
+            .map_err(CommandExecuteError::from)?;
+
+        Ok(())
+    }
+}
+
+impl Default for CommandRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::fmt::Debug for CommandRegistry {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CommandRegistry")
+            .field(
+                "commands",
+                &self
+                    .handlers
+                    .keys()
+                    .map(|(_, name)| name)
+                    .collect::<Vec<_>>(),
+            )
+            .field("auto_defer", &self.auto_defer)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns whether any of the options, or the options of a subcommand, has
+/// autocomplete.
+fn has_autocomplete(options: &[CommandOption]) -> bool {
+    options.iter().any(|option| {
+        option.autocomplete == Some(true) || option.options.as_deref().is_some_and(has_autocomplete)
+    })
+}
+
+fn log_command_error(
+    _context: Context,
+    interaction: Interaction,
+    error: CommandError,
+) -> BoxFuture<'static, ()> {
+    Box::pin(async move {
+        tracing::error!(?error, interaction_id = %interaction.id, "failed to handle command");
+    })
+}
+
+fn execute_command<'a, C>(
+    context: &'a Context,
+    interaction: &'a Interaction,
+    data: &'a ApplicationCommandData,
+) -> BoxFuture<'a, Result<(), CommandError>>
+where
+    C: Command + CommandOptions + Send + Sync + 'static,
+{
+    Box::pin(async move {
+        let command = C::parse_command_data(data)?;
+        command.execute(context, interaction).await?;
+
+        Ok(())
+    })
+}
+
+fn defer_ephemeral_command<C: CommandData>(data: &ApplicationCommandData) -> Option<bool> {
+    C::defer_ephemeral(&data.options)
+}
+
+fn execute_commands<'a, C>(
+    context: &'a Context,
+    interaction: &'a Interaction,
+    data: &'a ApplicationCommandData,
+) -> BoxFuture<'a, Result<(), CommandError>>
+where
+    C: Commands + Send + Sync + 'static,
+{
+    Box::pin(async move {
+        let command = C::parse_from_command_data(data)?;
+        command.execute(context, interaction).await?;
+
+        Ok(())
+    })
+}
+
+fn autocomplete_command<'a, C>(
+    context: &'a Context,
+    interaction: &'a Interaction,
+    data: &'a ApplicationCommandData,
+) -> BoxFuture<'a, Result<Vec<CommandOptionChoice>, CommandError>>
+where
+    C: CommandData + Send + 'static,
+{
+    C::dispatch_autocomplete(context, interaction, &data.options, data.resolved.as_ref())
+}
+
+fn autocomplete_commands<'a, C>(
+    context: &'a Context,
+    interaction: &'a Interaction,
+    data: &'a ApplicationCommandData,
+) -> BoxFuture<'a, Result<Vec<CommandOptionChoice>, CommandError>>
+where
+    C: Commands + 'static,
+{
+    Box::pin(C::autocomplete(
+        context,
+        interaction,
+        &data.name,
+        &data.options,
+        data.resolved.as_ref(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::application::{
+        command::{Command as TwilightCommand, CommandOptionChoice, CommandType},
+        interaction::{
+            Interaction, InteractionDataResolved,
+            application_command::{CommandData as ApplicationCommandData, CommandDataOption},
+        },
+    };
+    use twilight_util::builder::command::{CommandBuilder, StringBuilder, SubCommandBuilder};
+
+    use crate::{
+        Context,
+        command::{CommandError, CommandExecuteError, CommandRegistry, Commands},
+    };
+
+    fn search_command(autocomplete: bool) -> TwilightCommand {
+        CommandBuilder::new("search", "Searches the rules", CommandType::ChatInput)
+            .option(
+                SubCommandBuilder::new("rules", "Searches the rules")
+                    .option(StringBuilder::new("query", "The query").autocomplete(autocomplete)),
+            )
+            .build()
+    }
+
+    fn ping_command() -> TwilightCommand {
+        CommandBuilder::new("ping", "Pings the bot", CommandType::ChatInput).build()
+    }
+
+    /// A set of commands, given as a list of definitions, that never run.
+    macro_rules! command_set {
+        ($name:ident => $commands:expr) => {
+            struct $name;
+
+            #[async_trait::async_trait]
+            impl Commands for $name {
+                fn all_commands() -> Vec<TwilightCommand> {
+                    $commands
+                }
+
+                fn parse_from_command_data(
+                    data: &ApplicationCommandData,
+                ) -> Result<Self, CommandError> {
+                    Err(CommandError::UnknownCommand(data.name.clone()))
+                }
+
+                async fn execute(
+                    &self,
+                    _context: &Context,
+                    _interaction: &Interaction,
+                ) -> Result<(), CommandExecuteError> {
+                    Ok(())
+                }
+
+                async fn autocomplete(
+                    _context: &Context,
+                    _interaction: &Interaction,
+                    name: &str,
+                    _options: &[CommandDataOption],
+                    _resolved: Option<&InteractionDataResolved>,
+                ) -> Result<Vec<CommandOptionChoice>, CommandError> {
+                    Err(CommandError::MissingAutocomplete(name.to_string()))
+                }
+            }
+        };
+    }
+
+    command_set!(Moderation => vec![ping_command(), search_command(true)]);
+    command_set!(Search => vec![search_command(false)]);
+    command_set!(SearchMenu => vec![
+        CommandBuilder::new("search", "", CommandType::Message).build(),
+    ]);
+
+    #[test]
+    fn test_register_autocomplete() {
+        let registry = CommandRegistry::new().commands::<Moderation>();
+
+        assert_eq!(registry.all_commands().len(), 2);
+        assert!(registry.autocomplete_handlers.contains_key("search"));
+        assert!(!registry.autocomplete_handlers.contains_key("ping"));
+    }
+
+    #[test]
+    fn test_replace_command() {
+        let registry = CommandRegistry::new()
+            .commands::<Moderation>()
+            .commands::<Search>();
+
+        let names = registry
+            .all_commands()
+            .iter()
+            .map(|command| command.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["ping", "search"]);
+        assert!(!registry.autocomplete_handlers.contains_key("search"));
+    }
+
+    #[test]
+    fn test_context_menu_keeps_autocomplete() {
+        let registry = CommandRegistry::new()
+            .commands::<Moderation>()
+            .commands::<SearchMenu>();
+
+        assert_eq!(registry.all_commands().len(), 3);
+        assert!(registry.autocomplete_handlers.contains_key("search"));
+    }
+}
@@ -0,0 +1,336 @@
+use twilight_model::{
+    application::interaction::{Interaction, message_component::MessageComponentInteractionData},
+    id::Id,
+};
+
+use crate::{Context, command::CommandExecuteError, component::modal::ModalError};
+
+pub mod modal;
+pub mod registry;
+
+pub use registry::ComponentRegistry;
+
+/// The maximum length of a custom ID.
+pub const CUSTOM_ID_LENGTH_MAX: usize = 100;
+
+/// Separates the segments of a custom ID.
+const SEPARATOR: char = ':';
+
+/// A message component, such as a button or select menu, whose state is
+/// encoded into its custom ID.
+///
+/// Custom IDs are made up of the component's ID, its version and the encoded
+/// fields, such as `accept-rules:1:123456789`. Bumping the version whenever the
+/// fields change rejects components sent before the change, rather than
+/// decoding them into the wrong fields.
+pub trait ComponentData: Sized {
+    const COMPONENT_ID: &'static str;
+    const COMPONENT_VERSION: u32;
+
+    /// Encodes the fields, in order.
+    fn encode_fields(&self) -> Vec<String>;
+
+    /// # Errors
+    ///
+    /// Returns a `CustomIdError` if the number of fields is wrong, or if a field
+    /// could not be decoded.
+    fn decode_fields(fields: &[&str]) -> Result<Self, CustomIdError>;
+
+    /// # Errors
+    ///
+    /// Returns a [`CustomIdError::TooLong`] error if the custom ID is longer than
+    /// [`CUSTOM_ID_LENGTH_MAX`].
+    fn custom_id(&self) -> Result<String, CustomIdError> {
+        let custom_id = [
+            Self::COMPONENT_ID.to_string(),
+            Self::COMPONENT_VERSION.to_string(),
+        ]
+        .into_iter()
+        .chain(self.encode_fields())
+        .collect::<Vec<_>>()
+        .join(&SEPARATOR.to_string());
+
+        let length = custom_id.chars().count();
+        if length > CUSTOM_ID_LENGTH_MAX {
+            return Err(CustomIdError::TooLong(length));
+        }
+
+        Ok(custom_id)
+    }
+
+    /// # Errors
+    ///
+    /// Returns a `CustomIdError` if the custom ID belongs to another component or
+    /// version, or if its fields could not be decoded.
+    fn parse_custom_id(custom_id: &str) -> Result<Self, CustomIdError> {
+        let mut segments = custom_id.split(SEPARATOR);
+
+        let id = segments.next().unwrap_or_default();
+        if id != Self::COMPONENT_ID {
+            return Err(CustomIdError::UnexpectedComponent(id.to_string()));
+        }
+
+        let version = segments.next().unwrap_or_default();
+        if version.parse::<u32>().ok() != Some(Self::COMPONENT_VERSION) {
+            return Err(CustomIdError::OutdatedVersion {
+                expected: Self::COMPONENT_VERSION,
+                found: version.to_string(),
+            });
+        }
+
+        Self::decode_fields(&segments.collect::<Vec<_>>())
+    }
+}
+
+#[async_trait::async_trait]
+pub trait Component {
+    /// Handles a click on a button, or a selection in a select menu, whose
+    /// selected values are in `data`.
+    async fn execute(
+        &self,
+        context: &Context,
+        interaction: &Interaction,
+        data: &MessageComponentInteractionData,
+    ) -> Result<(), CommandExecuteError>;
+}
+
+/// A value that can be stored in a custom ID.
+pub trait CustomIdField: Sized {
+    /// Encodes the value. The result may contain the separator, which is
+    /// escaped when building the custom ID.
+    fn encode(&self) -> String;
+
+    fn decode(value: &str) -> Option<Self>;
+}
+
+impl CustomIdField for String {
+    fn encode(&self) -> String {
+        self.clone()
+    }
+
+    fn decode(value: &str) -> Option<Self> {
+        Some(value.to_string())
+    }
+}
+
+impl CustomIdField for bool {
+    fn encode(&self) -> String {
+        String::from(if *self { "1" } else { "0" })
+    }
+
+    fn decode(value: &str) -> Option<Self> {
+        match value {
+            "1" => Some(true),
+            "0" => Some(false),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_custom_id_field {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl CustomIdField for $ty {
+                fn encode(&self) -> String {
+                    self.to_string()
+                }
+
+                fn decode(value: &str) -> Option<Self> {
+                    value.parse().ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_custom_id_field!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+impl<T> CustomIdField for Id<T> {
+    fn encode(&self) -> String {
+        self.get().to_string()
+    }
+
+    fn decode(value: &str) -> Option<Self> {
+        value.parse().ok().and_then(Self::new_checked)
+    }
+}
+
+/// `None` is encoded as an empty field, so a value encoded as an empty string,
+/// such as `Some(String::new())`, decodes as `None`.
+impl<T: CustomIdField> CustomIdField for Option<T> {
+    fn encode(&self) -> String {
+        self.as_ref().map(T::encode).unwrap_or_default()
+    }
+
+    fn decode(value: &str) -> Option<Self> {
+        if value.is_empty() {
+            return Some(None);
+        }
+
+        T::decode(value).map(Some)
+    }
+}
+
+/// Encodes a field, escaping the separator.
+pub fn encode_field<T: CustomIdField>(value: &T) -> String {
+    value.encode().replace('%', "%25").replace(SEPARATOR, "%3A")
+}
+
+/// # Errors
+///
+/// Returns a [`CustomIdError::InvalidField`] error if the value could not be
+/// decoded.
+pub fn decode_field<T: CustomIdField>(name: &str, value: &str) -> Result<T, CustomIdError> {
+    T::decode(&value.replace("%3A", ":").replace("%25", "%"))
+        .ok_or_else(|| CustomIdError::InvalidField(name.to_string()))
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ComponentError {
+    #[error("Could not find component for custom ID {0}")]
+    UnknownComponent(String),
+    #[error("Could not find modal {0}")]
+    UnknownModal(String),
+    #[error(transparent)]
+    CommandExecuteError(#[from] CommandExecuteError),
+    #[error(transparent)]
+    CustomIdError(#[from] CustomIdError),
+    #[error(transparent)]
+    ModalError(#[from] ModalError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CustomIdError {
+    #[error("Custom ID is {0} characters long, more than the limit of 100")]
+    TooLong(usize),
+    #[error("Custom ID belongs to component {0}")]
+    UnexpectedComponent(String),
+    #[error("Custom ID has version {found}, expected {expected}")]
+    OutdatedVersion { expected: u32, found: String },
+    #[error("Custom ID has {found} fields, expected {expected}")]
+    UnexpectedFieldCount { expected: usize, found: usize },
+    #[error("Invalid value for field {0}")]
+    InvalidField(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::id::{Id, marker::UserMarker};
+
+    use crate::component::{
+        CUSTOM_ID_LENGTH_MAX, ComponentData, CustomIdError, decode_field, encode_field,
+    };
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Ban {
+        user_id: Id<UserMarker>,
+        reason: Option<String>,
+    }
+
+    impl ComponentData for Ban {
+        const COMPONENT_ID: &'static str = "ban";
+        const COMPONENT_VERSION: u32 = 2;
+
+        fn encode_fields(&self) -> Vec<String> {
+            vec![encode_field(&self.user_id), encode_field(&self.reason)]
+        }
+
+        fn decode_fields(fields: &[&str]) -> Result<Self, CustomIdError> {
+            let [user_id, reason] = fields else {
+                return Err(CustomIdError::UnexpectedFieldCount {
+                    expected: 2,
+                    found: fields.len(),
+                });
+            };
+
+            Ok(Self {
+                user_id: decode_field("user_id", user_id)?,
+                reason: decode_field("reason", reason)?,
+            })
+        }
+    }
+
+    #[test]
+    fn test_encode_field_escapes_separator() {
+        assert_eq!(encode_field(&String::from("a:b")), "a%3Ab");
+        assert_eq!(encode_field(&String::from("100%")), "100%25");
+        assert_eq!(encode_field(&String::from("%3A")), "%253A");
+    }
+
+    #[test]
+    fn test_decode_field_round_trip() {
+        for value in ["a:b", "100%", "%3A", "%25", "::%%", ""] {
+            let encoded = encode_field(&value.to_string());
+            assert!(!encoded.contains(':'));
+            assert_eq!(decode_field::<String>("value", &encoded).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_decode_optional_field() {
+        assert_eq!(encode_field(&None::<u64>), "");
+        assert_eq!(decode_field::<Option<u64>>("value", "").unwrap(), None);
+        assert_eq!(decode_field::<Option<u64>>("value", "5").unwrap(), Some(5));
+    }
+
+    #[test]
+    fn test_decode_invalid_field() {
+        assert!(matches!(
+            decode_field::<bool>("enabled", "yes"),
+            Err(CustomIdError::InvalidField(name)) if name == "enabled"
+        ));
+        assert!(matches!(
+            decode_field::<Id<UserMarker>>("user_id", "0"),
+            Err(CustomIdError::InvalidField(_))
+        ));
+    }
+
+    #[test]
+    fn test_custom_id_round_trip() {
+        let ban = Ban {
+            user_id: Id::new(123),
+            reason: Some(String::from("spam: again")),
+        };
+
+        let custom_id = ban.custom_id().unwrap();
+        assert_eq!(custom_id, "ban:2:123:spam%3A again");
+        assert_eq!(Ban::parse_custom_id(&custom_id).unwrap(), ban);
+    }
+
+    #[test]
+    fn test_custom_id_too_long() {
+        let ban = Ban {
+            user_id: Id::new(123),
+            reason: Some("a".repeat(CUSTOM_ID_LENGTH_MAX)),
+        };
+
+        assert!(matches!(ban.custom_id(), Err(CustomIdError::TooLong(_))));
+    }
+
+    #[test]
+    fn test_parse_unexpected_component() {
+        assert!(matches!(
+            Ban::parse_custom_id("kick:2:123:"),
+            Err(CustomIdError::UnexpectedComponent(id)) if id == "kick"
+        ));
+    }
+
+    #[test]
+    fn test_parse_outdated_version() {
+        assert!(matches!(
+            Ban::parse_custom_id("ban:1:123:"),
+            Err(CustomIdError::OutdatedVersion { expected: 2, found }) if found == "1"
+        ));
+    }
+
+    #[test]
+    fn test_parse_unexpected_field_count() {
+        assert!(matches!(
+            Ban::parse_custom_id("ban:2:123"),
+            Err(CustomIdError::UnexpectedFieldCount {
+                expected: 2,
+                found: 1
+            })
+        ));
+    }
+}
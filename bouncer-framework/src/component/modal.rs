@@ -0,0 +1,58 @@
+use twilight_model::application::interaction::{Interaction, modal::ModalInteractionData};
+
+use crate::{Context, command::CommandExecuteError, exts::interaction::InteractionModal};
+
+/// A modal made of text inputs, identified by its custom ID.
+pub trait ModalData: Sized {
+    const MODAL_ID: &'static str;
+
+    /// Builds the modal, with every input empty.
+    fn modal() -> InteractionModal;
+
+    /// # Errors
+    ///
+    /// Returns a `ModalError` if the submission is for another modal, or if a
+    /// required input is missing.
+    fn parse_modal_data(data: &ModalInteractionData) -> Result<Self, ModalError>;
+}
+
+#[async_trait::async_trait]
+pub trait Modal {
+    /// Handles the submission of the modal.
+    async fn execute(
+        &self,
+        context: &Context,
+        interaction: &Interaction,
+    ) -> Result<(), CommandExecuteError>;
+}
+
+/// Returns the value of an input, or `None` if it was left empty.
+#[must_use]
+pub fn parse_optional_input(data: &ModalInteractionData, custom_id: &str) -> Option<String> {
+    data.components
+        .iter()
+        .flat_map(|row| &row.components)
+        .find(|component| component.custom_id == custom_id)
+        .and_then(|component| component.value.clone())
+        .filter(|value| !value.is_empty())
+}
+
+/// # Errors
+///
+/// Returns a [`ModalError::MissingInput`] error if the input is missing or was
+/// left empty.
+pub fn parse_required_input(
+    data: &ModalInteractionData,
+    custom_id: &str,
+) -> Result<String, ModalError> {
+    parse_optional_input(data, custom_id)
+        .ok_or_else(|| ModalError::MissingInput(custom_id.to_string()))
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ModalError {
+    #[error("Submission belongs to modal {0}")]
+    UnexpectedModal(String),
+    #[error("Missing input {0}")]
+    MissingInput(String),
+}
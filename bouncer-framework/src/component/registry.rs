@@ -0,0 +1,421 @@
+use std::{collections::HashMap, sync::Arc};
+
+use core::future::Future;
+
+use twilight_model::application::interaction::{
+    Interaction, InteractionData, message_component::MessageComponentInteractionData,
+    modal::ModalInteractionData,
+};
+
+use crate::{
+    Context,
+    command::registry::BoxFuture,
+    component::{
+        Component, ComponentData, ComponentError, SEPARATOR,
+        modal::{Modal, ModalData},
+    },
+};
+
+type ComponentHandler = for<'a> fn(
+    &'a Context,
+    &'a Interaction,
+    &'a MessageComponentInteractionData,
+) -> BoxFuture<'a, Result<(), ComponentError>>;
+
+type ModalHandler = for<'a> fn(
+    &'a Context,
+    &'a Interaction,
+    &'a ModalInteractionData,
+) -> BoxFuture<'a, Result<(), ComponentError>>;
+
+type ErrorHandler =
+    Arc<dyn Fn(Context, Interaction, ComponentError) -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// Looks up message components by the ID their custom ID starts with, decodes
+/// their state and executes them, and does the same for modal submissions.
+///
+/// A registry is handed to [`ClientBuilder::component_registry`], after which
+/// the client handles message component and modal submit interactions on its
+/// own.
+///
+/// [`ClientBuilder::component_registry`]: crate::client::ClientBuilder::component_registry
+pub struct ComponentRegistry {
+    handlers: HashMap<&'static str, ComponentHandler>,
+    modal_handlers: HashMap<&'static str, ModalHandler>,
+    error_handler: ErrorHandler,
+}
+
+impl ComponentRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            handlers: HashMap::new(),
+            modal_handlers: HashMap::new(),
+            error_handler: Arc::new(log_component_error),
+        }
+    }
+
+    /// Registers a component.
+    #[must_use]
+    pub fn component<C>(mut self) -> Self
+    where
+        C: Component + ComponentData + Send + Sync + 'static,
+    {
+        self.handlers
+            .insert(C::COMPONENT_ID, execute_component::<C>);
+
+        self
+    }
+
+    /// Registers a modal, whose submissions are routed by the modal's ID.
+    #[must_use]
+    pub fn modal<M>(mut self) -> Self
+    where
+        M: Modal + ModalData + Send + Sync + 'static,
+    {
+        self.modal_handlers.insert(M::MODAL_ID, execute_modal::<M>);
+
+        self
+    }
+
+    /// Sets the hook called when a component or modal fails to decode or execute.
+    ///
+    /// By default, errors are logged.
+    #[must_use]
+    pub fn on_error<F, Fut>(mut self, error_handler: F) -> Self
+    where
+        F: Fn(Context, Interaction, ComponentError) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.error_handler = Arc::new(
+            move |context: Context,
+                  interaction: Interaction,
+                  error: ComponentError|
+                  -> BoxFuture<'static, ()> {
+                Box::pin(error_handler(context, interaction, error))
+            },
+        );
+
+        self
+    }
+
+    /// Executes the component or modal an interaction's custom ID refers to,
+    /// reporting any error through the error hook.
+    ///
+    /// Interactions that aren't message components or modal submissions are
+    /// ignored.
+    pub async fn handle(&self, context: &Context, interaction: &Interaction) {
+        if !matches!(
+            interaction.data,
+            Some(InteractionData::MessageComponent(_) | InteractionData::ModalSubmit(_))
+        ) {
+            return;
+        }
+
+        // Tracked until the error hook has run, so its replies follow up on
+        // any response the handler sent.
+        let _tracked = context.interaction_responses.track(interaction.id);
+        let result = match interaction.data.as_ref() {
+            Some(InteractionData::MessageComponent(data)) => {
+                self.handle_component(context, interaction, data).await
+            }
+            Some(InteractionData::ModalSubmit(data)) => {
+                self.handle_modal(context, interaction, data).await
+            }
+            _ => return,
+        };
+
+        if let Err(error) = result {
+            (self.error_handler)(context.clone(), interaction.clone(), error).await;
+        }
+    }
+
+    async fn handle_component(
+        &self,
+        context: &Context,
+        interaction: &Interaction,
+        data: &MessageComponentInteractionData,
+    ) -> Result<(), ComponentError> {
+        let id = data
+            .custom_id
+            .split_once(SEPARATOR)
+            .map_or(data.custom_id.as_str(), |(id, _)| id);
+        let handler = self
+            .handlers
+            .get(id)
+            .ok_or_else(|| ComponentError::UnknownComponent(data.custom_id.clone()))?;
+
+        handler(context, interaction, data).await
+    }
+
+    async fn handle_modal(
+        &self,
+        context: &Context,
+        interaction: &Interaction,
+        data: &ModalInteractionData,
+    ) -> Result<(), ComponentError> {
+        let handler = self
+            .modal_handlers
+            .get(data.custom_id.as_str())
+            .ok_or_else(|| ComponentError::UnknownModal(data.custom_id.clone()))?;
+
+        handler(context, interaction, data).await
+    }
+}
+
+impl Default for ComponentRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::fmt::Debug for ComponentRegistry {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ComponentRegistry")
+            .field("components", &self.handlers.keys().collect::<Vec<_>>())
+            .field("modals", &self.modal_handlers.keys().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+fn log_component_error(
+    _context: Context,
+    interaction: Interaction,
+    error: ComponentError,
+) -> BoxFuture<'static, ()> {
+    Box::pin(async move {
+        tracing::error!(
+            ?error,
+            interaction_id = %interaction.id,
+            "failed to handle component or modal"
+        );
+    })
+}
+
+fn execute_component<'a, C>(
+    context: &'a Context,
+    interaction: &'a Interaction,
+    data: &'a MessageComponentInteractionData,
+) -> BoxFuture<'a, Result<(), ComponentError>>
+where
+    C: Component + ComponentData + Send + Sync + 'static,
+{
+    Box::pin(async move {
+        let component = C::parse_custom_id(&data.custom_id)?;
+        component.execute(context, interaction, data).await?;
+
+        Ok(())
+    })
+}
+
+fn execute_modal<'a, M>(
+    context: &'a Context,
+    interaction: &'a Interaction,
+    data: &'a ModalInteractionData,
+) -> BoxFuture<'a, Result<(), ComponentError>>
+where
+    M: Modal + ModalData + Send + Sync + 'static,
+{
+    Box::pin(async move {
+        let modal = M::parse_modal_data(data)?;
+        modal.execute(context, interaction).await?;
+
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use twilight_gateway::ShardId;
+    use twilight_http::Client as HttpClient;
+    use twilight_model::{
+        application::interaction::{
+            Interaction, InteractionData, InteractionType,
+            message_component::MessageComponentInteractionData, modal::ModalInteractionData,
+        },
+        channel::message::component::ComponentType,
+        id::Id,
+        oauth::ApplicationIntegrationMap,
+    };
+
+    use crate::{
+        Context,
+        command::CommandExecuteError,
+        component::{
+            Component, ComponentData, CustomIdError,
+            modal::{Modal, ModalData, ModalError},
+            registry::ComponentRegistry,
+        },
+        exts::interaction::InteractionModal,
+    };
+
+    /// Fails when clicked, so that its execution reaches the error hook.
+    struct Confirm;
+
+    impl ComponentData for Confirm {
+        const COMPONENT_ID: &'static str = "confirm";
+        const COMPONENT_VERSION: u32 = 0;
+
+        fn encode_fields(&self) -> Vec<String> {
+            Vec::new()
+        }
+
+        fn decode_fields(_fields: &[&str]) -> Result<Self, CustomIdError> {
+            Ok(Self)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Component for Confirm {
+        async fn execute(
+            &self,
+            _context: &Context,
+            _interaction: &Interaction,
+            _data: &MessageComponentInteractionData,
+        ) -> Result<(), CommandExecuteError> {
+            Err(anyhow::anyhow!("confirmed").into())
+        }
+    }
+
+    /// Fails when submitted, so that its execution reaches the error hook.
+    struct Report;
+
+    impl ModalData for Report {
+        const MODAL_ID: &'static str = "report";
+
+        fn modal() -> InteractionModal {
+            InteractionModal::new(Self::MODAL_ID, "Report")
+        }
+
+        fn parse_modal_data(_data: &ModalInteractionData) -> Result<Self, ModalError> {
+            Ok(Self)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Modal for Report {
+        async fn execute(
+            &self,
+            _context: &Context,
+            _interaction: &Interaction,
+        ) -> Result<(), CommandExecuteError> {
+            Err(anyhow::anyhow!("reported").into())
+        }
+    }
+
+    #[allow(deprecated)]
+    fn interaction(kind: InteractionType, data: InteractionData) -> Interaction {
+        Interaction {
+            app_permissions: None,
+            application_id: Id::new(1),
+            authorizing_integration_owners: ApplicationIntegrationMap {
+                guild: None,
+                user: None,
+            },
+            channel: None,
+            channel_id: None,
+            context: None,
+            data: Some(data),
+            entitlements: Vec::new(),
+            guild: None,
+            guild_id: None,
+            guild_locale: None,
+            id: Id::new(1),
+            kind,
+            locale: None,
+            member: None,
+            message: None,
+            token: String::new(),
+            user: None,
+        }
+    }
+
+    fn component(custom_id: &str) -> Interaction {
+        interaction(
+            InteractionType::MessageComponent,
+            InteractionData::MessageComponent(Box::new(MessageComponentInteractionData {
+                custom_id: custom_id.to_owned(),
+                component_type: ComponentType::Button,
+                resolved: None,
+                values: Vec::new(),
+            })),
+        )
+    }
+
+    fn modal_submit(custom_id: &str) -> Interaction {
+        interaction(
+            InteractionType::ModalSubmit,
+            InteractionData::ModalSubmit(ModalInteractionData {
+                components: Vec::new(),
+                custom_id: custom_id.to_owned(),
+            }),
+        )
+    }
+
+    fn context() -> Context {
+        Context::new(
+            Arc::new(HttpClient::new(String::new())),
+            None,
+            Arc::default(),
+            Arc::default(),
+            Arc::default(),
+            Arc::default(),
+            ShardId::ONE,
+        )
+    }
+
+    /// Registers the component and modal, recording the errors they report.
+    fn registry() -> (ComponentRegistry, Arc<Mutex<Vec<String>>>) {
+        let errors = Arc::<Mutex<Vec<String>>>::default();
+        let registry = ComponentRegistry::new()
+            .component::<Confirm>()
+            .modal::<Report>()
+            .on_error({
+                let errors = Arc::clone(&errors);
+                move |_context, _interaction, error| {
+                    errors.lock().unwrap().push(error.to_string());
+                    async {}
+                }
+            });
+
+        (registry, errors)
+    }
+
+    #[tokio::test]
+    async fn test_route_by_custom_id() {
+        let (registry, errors) = registry();
+        let context = context();
+
+        registry.handle(&context, &component("confirm:0")).await;
+        registry.handle(&context, &component("confirm:1")).await;
+        registry.handle(&context, &component("missing:0")).await;
+
+        assert_eq!(
+            *errors.lock().unwrap(),
+            [
+                "An error occurred while executing the command: confirmed",
+                "Custom ID has version 1, expected 0",
+                "Could not find component for custom ID missing:0",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_route_modal_submissions() {
+        let (registry, errors) = registry();
+        let context = context();
+
+        registry.handle(&context, &modal_submit("report")).await;
+        registry.handle(&context, &modal_submit("feedback")).await;
+
+        assert_eq!(
+            *errors.lock().unwrap(),
+            [
+                "An error occurred while executing the command: reported",
+                "Could not find modal feedback",
+            ]
+        );
+    }
+}
@@ -1,16 +1,145 @@
 use std::sync::Arc;
 
-use twilight_cache_inmemory::InMemoryCache;
+use core::time::Duration;
+
+use twilight_gateway::ShardId;
 use twilight_http::Client as HttpClient;
+use twilight_model::{
+    application::interaction::{Interaction, InteractionData, InteractionType},
+    id::{Id, marker::MessageMarker},
+};
+
+use crate::{
+    cache::{Cache, SharedCache},
+    collector::{Collectors, InteractionCollector},
+    data::DataMap,
+    exts::interaction::{InteractionExt as _, InteractionResponses},
+    shard::{ShardStatus, ShardStatuses},
+};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Context {
     pub http: Arc<HttpClient>,
-    pub cache: Arc<InMemoryCache>,
+    /// The cache, unless caching is disabled.
+    pub cache: Option<SharedCache>,
+    pub interaction_responses: Arc<InteractionResponses>,
+    pub collectors: Arc<Collectors>,
+    pub shard_statuses: Arc<ShardStatuses>,
+    pub data: Arc<DataMap>,
+    /// The shard the event being handled was received on.
+    pub shard_id: ShardId,
 }
 
 impl Context {
-    pub const fn new(http: Arc<HttpClient>, cache: Arc<InMemoryCache>) -> Self {
-        Self { http, cache }
+    pub const fn new(
+        http: Arc<HttpClient>,
+        cache: Option<SharedCache>,
+        interaction_responses: Arc<InteractionResponses>,
+        collectors: Arc<Collectors>,
+        shard_statuses: Arc<ShardStatuses>,
+        data: Arc<DataMap>,
+        shard_id: ShardId,
+    ) -> Self {
+        Self {
+            http,
+            cache,
+            interaction_responses,
+            collectors,
+            shard_statuses,
+            data,
+            shard_id,
+        }
+    }
+
+    /// The cache, if caching is enabled and the cache is of type `C`, such as
+    /// [`InMemoryCache`](twilight_cache_inmemory::InMemoryCache) unless
+    /// another one was set with
+    /// [`ClientBuilder::cache`](crate::client::ClientBuilder::cache).
+    #[must_use]
+    pub fn cache<C: Cache>(&self) -> Option<Arc<C>> {
+        self.cache.as_ref()?.get()
+    }
+
+    /// The shared value of type `T` added with
+    /// [`ClientBuilder::data`](crate::client::ClientBuilder::data), if any.
+    #[must_use]
+    pub fn data<T: Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+        self.data.get()
+    }
+
+    /// The status of the shard the event being handled was received on.
+    #[must_use]
+    pub fn shard_status(&self) -> Option<ShardStatus> {
+        self.shard_statuses.get(self.shard_id.number())
+    }
+
+    /// Picks the string for the locale of the user who triggered
+    /// `interaction`, falling back to the guild's locale and then to
+    /// `default`.
+    ///
+    /// `localizations` are `(locale, string)` pairs, such as `("de", "Hallo")`.
+    /// This is the same as [`InteractionExt::localize`].
+    ///
+    /// [`InteractionExt::localize`]: crate::exts::interaction::InteractionExt::localize
+    #[must_use]
+    pub fn localize<'a>(
+        &self,
+        interaction: &Interaction,
+        localizations: &[(&str, &'a str)],
+        default: &'a str,
+    ) -> &'a str {
+        interaction.localize(localizations, default)
+    }
+
+    /// Collects the interactions `filter` matches, until the returned collector
+    /// is dropped.
+    ///
+    /// Collected interactions aren't routed to the command and component
+    /// registries, and must be responded to by the collector's owner.
+    pub fn collect_interactions(
+        &self,
+        filter: impl Fn(&Interaction) -> bool + Send + Sync + 'static,
+    ) -> InteractionCollector {
+        self.collectors.collect(filter)
+    }
+
+    /// Waits for the next click or selection on a component of a message that
+    /// `filter` matches, returning `None` once `timeout` has passed.
+    pub async fn await_component(
+        &self,
+        message_id: Id<MessageMarker>,
+        filter: impl Fn(&Interaction) -> bool + Send + Sync + 'static,
+        timeout: Duration,
+    ) -> Option<Interaction> {
+        self.collect_interactions(move |interaction| {
+            interaction.kind == InteractionType::MessageComponent
+                && interaction
+                    .message
+                    .as_ref()
+                    .is_some_and(|message| message.id == message_id)
+                && filter(interaction)
+        })
+        .next_interaction_timeout(timeout)
+        .await
+    }
+
+    /// Waits for the next submission of the modal with `custom_id` that
+    /// `filter` matches, returning `None` once `timeout` has passed.
+    pub async fn await_modal(
+        &self,
+        custom_id: impl Into<String>,
+        filter: impl Fn(&Interaction) -> bool + Send + Sync + 'static,
+        timeout: Duration,
+    ) -> Option<Interaction> {
+        let custom_id = custom_id.into();
+
+        self.collect_interactions(move |interaction| {
+            matches!(
+                &interaction.data,
+                Some(InteractionData::ModalSubmit(data)) if data.custom_id == custom_id
+            ) && filter(interaction)
+        })
+        .next_interaction_timeout(timeout)
+        .await
     }
 }
@@ -0,0 +1,60 @@
+use std::{collections::HashMap, sync::Arc};
+
+use core::any::{Any, TypeId};
+
+/// Application state shared with the handlers through the
+/// [`Context`](crate::Context), one value per type.
+#[derive(Default)]
+pub struct DataMap {
+    values: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl DataMap {
+    /// Inserts a value, replacing the previous value of its type.
+    pub fn insert<T: Send + Sync + 'static>(&mut self, value: T) {
+        self.values.insert(TypeId::of::<T>(), Arc::new(value));
+    }
+
+    #[must_use]
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+        Arc::clone(self.values.get(&TypeId::of::<T>())?)
+            .downcast()
+            .ok()
+    }
+}
+
+impl core::fmt::Debug for DataMap {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DataMap")
+            .field("values", &self.values.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::DataMap;
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Prefix(&'static str);
+
+    #[test]
+    fn test_get_by_type() {
+        let mut data = DataMap::default();
+        data.insert(Prefix("!"));
+        data.insert(42_u32);
+
+        assert_eq!(data.get::<Prefix>().as_deref(), Some(&Prefix("!")));
+        assert_eq!(data.get::<u32>().as_deref(), Some(&42));
+        assert!(data.get::<u64>().is_none());
+    }
+
+    #[test]
+    fn test_replace_value() {
+        let mut data = DataMap::default();
+        data.insert(Prefix("!"));
+        data.insert(Prefix("?"));
+
+        assert_eq!(data.get::<Prefix>().as_deref(), Some(&Prefix("?")));
+    }
+}
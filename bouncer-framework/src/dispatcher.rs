@@ -0,0 +1,419 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use core::{num::NonZeroUsize, time::Duration};
+
+use tokio::sync::{
+    OwnedSemaphorePermit, Semaphore,
+    mpsc::{self, Receiver, Sender, error::TrySendError},
+};
+use twilight_gateway::Event;
+use twilight_model::id::{Id, marker::GuildMarker};
+
+use crate::{
+    context::Context,
+    event_handler::EventHandler,
+    middleware::{Middleware, Next},
+    shard::ShardLifecycle,
+    shutdown::{InFlightGuard, InFlightTasks},
+};
+
+/// The number of events dispatched at once by default.
+pub const MAX_CONCURRENT_EVENTS: NonZeroUsize = NonZeroUsize::new(100).unwrap();
+/// How many events outside of guild queues can wait for a dispatch to finish
+/// before further ones are dropped.
+const MAX_WAITING_EVENTS: usize = 10_000;
+/// How many events a guild's queue holds before further ones are dropped.
+const GUILD_QUEUE_CAPACITY: usize = 1_000;
+/// How long a guild's queue waits for another event before it's torn down.
+const GUILD_QUEUE_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+type QueuedEvent = (Context, Event, InFlightGuard);
+
+/// Runs the middleware and event handlers for each event on its own task, so
+/// slow handlers don't hold up the gateway.
+pub(crate) struct EventDispatcher {
+    event_handlers: Vec<Box<dyn EventHandler>>,
+    middlewares: Vec<Box<dyn Middleware>>,
+    permits: Arc<Semaphore>,
+    waiting: Arc<Semaphore>,
+    in_flight: Arc<InFlightTasks>,
+    ordered_guild_events: bool,
+    guild_queues: Mutex<HashMap<Id<GuildMarker>, Sender<QueuedEvent>>>,
+}
+
+impl EventDispatcher {
+    pub(crate) fn new(
+        event_handlers: Vec<Box<dyn EventHandler>>,
+        middlewares: Vec<Box<dyn Middleware>>,
+        max_concurrent_events: NonZeroUsize,
+        in_flight: Arc<InFlightTasks>,
+        ordered_guild_events: bool,
+    ) -> Self {
+        Self {
+            event_handlers,
+            middlewares,
+            permits: Arc::new(Semaphore::new(max_concurrent_events.get())),
+            waiting: Arc::new(Semaphore::new(MAX_WAITING_EVENTS)),
+            in_flight,
+            ordered_guild_events,
+            guild_queues: Mutex::default(),
+        }
+    }
+
+    /// Spawns the dispatch of an event, which waits for a permit when the
+    /// concurrency limit is reached.
+    ///
+    /// With ordered guild events, guild events are instead queued behind the
+    /// earlier events of their guild.
+    ///
+    /// This never waits, so a slow handler can't hold up the gateway. Events
+    /// are dropped instead once too many are waiting.
+    pub(crate) fn dispatch(self: &Arc<Self>, context: Context, event: Event) {
+        let in_flight = self.in_flight.start();
+
+        if self.ordered_guild_events {
+            if let Some(guild_id) = event.guild_id() {
+                self.queue(guild_id, (context, event, in_flight));
+
+                return;
+            }
+        }
+
+        let Ok(waiting) = Arc::clone(&self.waiting).try_acquire_owned() else {
+            tracing::warn!(
+                event = ?event.kind(),
+                "too many events waiting to be dispatched, dropping event"
+            );
+
+            return;
+        };
+        let dispatcher = Arc::clone(self);
+        tokio::spawn(async move {
+            let Some(permit) = dispatcher.acquire_permit().await else {
+                return;
+            };
+            drop(waiting);
+            dispatcher.run_isolated(context, event).await;
+            drop(permit);
+            drop(in_flight);
+        });
+    }
+
+    fn queue(self: &Arc<Self>, guild_id: Id<GuildMarker>, queued_event: QueuedEvent) {
+        let mut guild_queues = self.lock_guild_queues();
+
+        let queued_event = match guild_queues.get(&guild_id) {
+            Some(sender) => match sender.try_send(queued_event) {
+                Ok(()) => return,
+                Err(TrySendError::Full((_, event, _))) => {
+                    tracing::warn!(
+                        %guild_id,
+                        event = ?event.kind(),
+                        "guild's event queue is full, dropping event"
+                    );
+
+                    return;
+                }
+                Err(TrySendError::Closed(queued_event)) => queued_event,
+            },
+            None => queued_event,
+        };
+
+        let (sender, receiver) = mpsc::channel(GUILD_QUEUE_CAPACITY);
+        // The queue is new and empty, so this can't fail.
+        let _ = sender.try_send(queued_event);
+        guild_queues.insert(guild_id, sender);
+        drop(guild_queues);
+
+        tokio::spawn(Arc::clone(self).run_guild_queue(guild_id, receiver));
+    }
+
+    async fn run_guild_queue(
+        self: Arc<Self>,
+        guild_id: Id<GuildMarker>,
+        mut receiver: Receiver<QueuedEvent>,
+    ) {
+        loop {
+            match tokio::time::timeout(GUILD_QUEUE_IDLE_TIMEOUT, receiver.recv()).await {
+                Ok(Some((context, event, in_flight))) => {
+                    // The permit is only taken once it's the event's turn, so
+                    // a slow guild holds up no more than one dispatch.
+                    let Some(permit) = self.acquire_permit().await else {
+                        return;
+                    };
+                    self.run_isolated(context, event).await;
+                    drop(permit);
+                    drop(in_flight);
+                }
+                Ok(None) => return,
+                Err(_) => {
+                    if self.remove_idle_guild_queue(guild_id, &receiver) {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Removes a guild's queue if nothing was queued since it went idle.
+    ///
+    /// Events are queued with the lock held, so none can be lost between
+    /// checking the queue and removing it.
+    fn remove_idle_guild_queue(
+        &self,
+        guild_id: Id<GuildMarker>,
+        receiver: &Receiver<QueuedEvent>,
+    ) -> bool {
+        let mut guild_queues = self.lock_guild_queues();
+
+        let is_idle = receiver.is_empty();
+        if is_idle {
+            guild_queues.remove(&guild_id);
+        }
+
+        is_idle
+    }
+
+    /// Runs the middleware and event handlers on a task of their own, so a
+    /// panicking handler is logged instead of taking down the caller.
+    async fn run_isolated(self: &Arc<Self>, context: Context, event: Event) {
+        let kind = event.kind();
+        let dispatcher = Arc::clone(self);
+
+        let result = tokio::spawn(async move {
+            Next::new(&dispatcher.middlewares, &dispatcher.event_handlers)
+                .run(context, event)
+                .await;
+        })
+        .await;
+
+        if let Err(error) = result {
+            if error.is_panic() {
+                tracing::error!(event = ?kind, "event handler panicked");
+            }
+        }
+    }
+
+    /// Spawns the dispatch of a shard lifecycle change to each event handler,
+    /// in the order they were added, bypassing the middleware.
+    pub(crate) fn dispatch_lifecycle(
+        self: &Arc<Self>,
+        context: Context,
+        lifecycle: ShardLifecycle,
+    ) {
+        let in_flight = self.in_flight.start();
+        let dispatcher = Arc::clone(self);
+
+        tokio::spawn(async move {
+            for event_handler in &dispatcher.event_handlers {
+                event_handler
+                    .shard_lifecycle(context.clone(), lifecycle)
+                    .await;
+            }
+            drop(in_flight);
+        });
+    }
+
+    /// Runs each event handler's shutdown hook, in the order they were added.
+    pub(crate) async fn shutdown(&self) {
+        for event_handler in &self.event_handlers {
+            event_handler.shutdown().await;
+        }
+    }
+
+    async fn acquire_permit(&self) -> Option<OwnedSemaphorePermit> {
+        // The semaphore is never closed, so this always succeeds.
+        Arc::clone(&self.permits).acquire_owned().await.ok()
+    }
+
+    fn lock_guild_queues(&self) -> MutexGuard<'_, HashMap<Id<GuildMarker>, Sender<QueuedEvent>>> {
+        self.guild_queues
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl core::fmt::Debug for EventDispatcher {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("EventDispatcher")
+            .field("event_handlers", &self.event_handlers.len())
+            .field("middlewares", &self.middlewares.len())
+            .field("permits", &self.permits)
+            .field("ordered_guild_events", &self.ordered_guild_events)
+            .field("guild_queues", &self.lock_guild_queues().len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use core::{num::NonZeroUsize, time::Duration};
+
+    use tokio::sync::watch;
+    use twilight_gateway::{Event, ShardId};
+    use twilight_http::Client as HttpClient;
+    use twilight_model::{gateway::payload::incoming::RoleDelete, id::Id};
+
+    use crate::{
+        context::Context,
+        dispatcher::{EventDispatcher, GUILD_QUEUE_CAPACITY},
+        event_handler::EventHandler,
+    };
+
+    const BLOCKED_GUILD: u64 = 1;
+    const PANICKING_ROLE: u64 = u64::MAX;
+
+    /// Records the deleted roles, once the gate is open for those of the
+    /// blocked guild.
+    struct Recorder {
+        gate: watch::Receiver<bool>,
+        roles: Arc<Mutex<Vec<u64>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl EventHandler for Recorder {
+        async fn role_delete(&self, _context: Context, role_delete: RoleDelete) {
+            if role_delete.guild_id.get() == BLOCKED_GUILD {
+                let _ = self.gate.clone().wait_for(|open| *open).await;
+            }
+            assert_ne!(role_delete.role_id.get(), PANICKING_ROLE);
+
+            self.roles.lock().unwrap().push(role_delete.role_id.get());
+        }
+    }
+
+    struct Harness {
+        dispatcher: Arc<EventDispatcher>,
+        gate: watch::Sender<bool>,
+        roles: Arc<Mutex<Vec<u64>>>,
+    }
+
+    impl Harness {
+        fn new(max_concurrent_events: usize, ordered_guild_events: bool) -> Self {
+            let (gate, receiver) = watch::channel(false);
+            let roles = Arc::<Mutex<Vec<u64>>>::default();
+            let recorder = Recorder {
+                gate: receiver,
+                roles: Arc::clone(&roles),
+            };
+
+            Self {
+                dispatcher: Arc::new(EventDispatcher::new(
+                    vec![Box::new(recorder)],
+                    Vec::new(),
+                    NonZeroUsize::new(max_concurrent_events).unwrap(),
+                    Arc::default(),
+                    ordered_guild_events,
+                )),
+                gate,
+                roles,
+            }
+        }
+
+        fn dispatch(&self, guild_id: u64, role_id: u64) {
+            let context = Context::new(
+                Arc::new(HttpClient::new(String::new())),
+                None,
+                Arc::default(),
+                Arc::default(),
+                Arc::default(),
+                Arc::default(),
+                ShardId::ONE,
+            );
+            let event = Event::RoleDelete(RoleDelete {
+                guild_id: Id::new(guild_id),
+                role_id: Id::new(role_id),
+            });
+
+            self.dispatcher.dispatch(context, event);
+        }
+
+        fn open_gate(&self) {
+            self.gate.send_replace(true);
+        }
+
+        /// Waits until `count` roles were recorded, returning them.
+        async fn roles(&self, count: usize) -> Vec<u64> {
+            tokio::time::timeout(Duration::from_secs(5), async {
+                while self.roles.lock().unwrap().len() < count {
+                    tokio::time::sleep(Duration::from_millis(1)).await;
+                }
+            })
+            .await
+            .unwrap();
+
+            self.roles.lock().unwrap().clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ordered_guild_events() {
+        let harness = Harness::new(10, true);
+
+        for role_id in [1, 2, 3] {
+            harness.dispatch(BLOCKED_GUILD, role_id);
+        }
+        harness.open_gate();
+
+        assert_eq!(harness.roles(3).await, [1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_slow_guild_doesnt_hold_up_others() {
+        let harness = Harness::new(2, true);
+
+        for role_id in [1, 2, 3, 4] {
+            harness.dispatch(BLOCKED_GUILD, role_id);
+        }
+        harness.dispatch(2, 10);
+        harness.dispatch(3, 20);
+        assert_eq!(harness.roles(2).await, [10, 20]);
+
+        harness.open_gate();
+        assert_eq!(harness.roles(6).await, [10, 20, 1, 2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_stuck_handlers_dont_hold_up_dispatch() {
+        let harness = Harness::new(1, false);
+
+        harness.dispatch(BLOCKED_GUILD, 1);
+        harness.dispatch(2, 10);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(harness.roles.lock().unwrap().is_empty());
+
+        harness.open_gate();
+        assert_eq!(harness.roles(2).await, [1, 10]);
+    }
+
+    #[tokio::test]
+    async fn test_full_guild_queue_drops_events() {
+        let harness = Harness::new(1, true);
+
+        let event_count = GUILD_QUEUE_CAPACITY as u64 + 10;
+        for role_id in 1..=event_count {
+            harness.dispatch(BLOCKED_GUILD, role_id);
+        }
+        harness.open_gate();
+
+        let roles = harness.roles(GUILD_QUEUE_CAPACITY).await;
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(roles.len(), GUILD_QUEUE_CAPACITY);
+        assert!(roles.is_sorted());
+    }
+
+    #[tokio::test]
+    async fn test_panicking_handler_is_isolated() {
+        let harness = Harness::new(1, true);
+
+        harness.dispatch(2, PANICKING_ROLE);
+        harness.dispatch(2, 10);
+
+        assert_eq!(harness.roles(1).await, [10]);
+    }
+}
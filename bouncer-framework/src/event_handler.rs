@@ -1,21 +1,82 @@
-use crate::context::Context;
+use crate::{context::Context, shard::ShardLifecycle};
 
 use async_trait::async_trait;
-use twilight_gateway::{Event, EventTypeFlags};
-use twilight_model::gateway::payload::incoming::{InteractionCreate, Ready};
+use twilight_gateway::{Event, EventTypeFlags, Intents};
+use twilight_model::gateway::payload::incoming::{
+    AutoModerationActionExecution, AutoModerationRuleCreate, AutoModerationRuleDelete,
+    AutoModerationRuleUpdate, BanAdd, BanRemove, ChannelCreate, ChannelDelete, ChannelPinsUpdate,
+    ChannelUpdate, CommandPermissionsUpdate, EntitlementCreate, EntitlementDelete,
+    EntitlementUpdate, GuildAuditLogEntryCreate, GuildCreate, GuildDelete, GuildEmojisUpdate,
+    GuildIntegrationsUpdate, GuildScheduledEventCreate, GuildScheduledEventDelete,
+    GuildScheduledEventUpdate, GuildScheduledEventUserAdd, GuildScheduledEventUserRemove,
+    GuildStickersUpdate, GuildUpdate, IntegrationCreate, IntegrationDelete, IntegrationUpdate,
+    InteractionCreate, InviteCreate, InviteDelete, MemberAdd, MemberChunk, MemberRemove,
+    MemberUpdate, MessageCreate, MessageDelete, MessageDeleteBulk, MessagePollVoteAdd,
+    MessagePollVoteRemove, MessageUpdate, PresenceUpdate, ReactionAdd, ReactionRemove,
+    ReactionRemoveAll, ReactionRemoveEmoji, Ready, RoleCreate, RoleDelete, RoleUpdate,
+    StageInstanceCreate, StageInstanceDelete, StageInstanceUpdate, ThreadCreate, ThreadDelete,
+    ThreadListSync, ThreadMemberUpdate, ThreadMembersUpdate, ThreadUpdate, TypingStart,
+    UnavailableGuild, UserUpdate, VoiceServerUpdate, VoiceStateUpdate, WebhooksUpdate,
+};
 
 macro_rules! create_event_handlers {
-    ($($event_name:ident ($arg_type:ty)),* $(,)?) => {
+    ($($event_name:ident ($arg_type:ty) => [$($intent:ident),*]),* $(,)?) => {
         paste::paste! {
+            /// Handles the events received from the gateway.
+            ///
+            /// Every event has a no-op default, so handlers only implement the
+            /// events they use.
             #[async_trait]
             pub trait EventHandler: Send + Sync {
                     $(
-                        async fn [<$event_name:snake>](&self, context: Context, [<$event_name:snake>]: $arg_type);
+                        async fn [<$event_name:snake>](&self, context: Context, [<$event_name:snake>]: $arg_type) {
+                            let _ = (context, [<$event_name:snake>]);
+                        }
                     )*
 
+                    /// Called when a shard disconnects, fails to reconnect, or
+                    /// resumes its session.
+                    async fn shard_lifecycle(&self, context: Context, lifecycle: ShardLifecycle) {
+                        let _ = (context, lifecycle);
+                    }
+
+                    /// Called once the client has stopped receiving events and
+                    /// the in-flight ones are handled or timed out, to flush
+                    /// any state before exiting.
+                    async fn shutdown(&self) {}
+
+                    /// The events to deserialize, every event by default.
+                    ///
+                    /// `#[bouncer_macros::event_handler]` on the implementation
+                    /// narrows this to the events it handles, which skips
+                    /// deserializing the others. The events the cache needs
+                    /// are received regardless.
                     fn used_event_flags(&self) -> EventTypeFlags {
                         $(EventTypeFlags::[<$event_name:snake:upper>])|*
                     }
+
+                    /// The intents the client requests on top of the ones it's
+                    /// built with, none by default.
+                    ///
+                    /// `#[bouncer_macros::event_handler]` on the implementation
+                    /// sets this to the intents the events it handles need.
+                    fn required_intents(&self) -> Intents {
+                        Intents::empty()
+                    }
+            }
+
+            /// The intents needed to receive each event, named after its event
+            /// type flag.
+            ///
+            /// These are kept apart from the flags as some flags share bits,
+            /// such as `READY` and `MESSAGE_POLL_VOTE_ADD`.
+            pub mod event_intents {
+                use twilight_gateway::Intents;
+
+                $(
+                    pub const [<$event_name:snake:upper>]: Intents =
+                        Intents::from_bits_truncate(0 $(| Intents::$intent.bits())*);
+                )*
             }
 
             #[async_trait]
@@ -34,11 +95,156 @@ macro_rules! create_event_handlers {
 }
 
 create_event_handlers! {
-    Ready(Box<Ready>),
-    InteractionCreate(Box<InteractionCreate>)
+    AutoModerationActionExecution(AutoModerationActionExecution) => [AUTO_MODERATION_EXECUTION],
+    AutoModerationRuleCreate(AutoModerationRuleCreate) => [AUTO_MODERATION_CONFIGURATION],
+    AutoModerationRuleDelete(AutoModerationRuleDelete) => [AUTO_MODERATION_CONFIGURATION],
+    AutoModerationRuleUpdate(AutoModerationRuleUpdate) => [AUTO_MODERATION_CONFIGURATION],
+    BanAdd(BanAdd) => [GUILD_MODERATION],
+    BanRemove(BanRemove) => [GUILD_MODERATION],
+    ChannelCreate(Box<ChannelCreate>) => [GUILDS],
+    ChannelDelete(Box<ChannelDelete>) => [GUILDS],
+    ChannelPinsUpdate(ChannelPinsUpdate) => [GUILDS],
+    ChannelUpdate(Box<ChannelUpdate>) => [GUILDS],
+    CommandPermissionsUpdate(CommandPermissionsUpdate) => [],
+    EntitlementCreate(EntitlementCreate) => [],
+    EntitlementDelete(EntitlementDelete) => [],
+    EntitlementUpdate(EntitlementUpdate) => [],
+    GuildAuditLogEntryCreate(Box<GuildAuditLogEntryCreate>) => [GUILD_MODERATION],
+    GuildCreate(Box<GuildCreate>) => [GUILDS],
+    GuildDelete(GuildDelete) => [GUILDS],
+    GuildEmojisUpdate(GuildEmojisUpdate) => [GUILD_EMOJIS_AND_STICKERS],
+    GuildIntegrationsUpdate(GuildIntegrationsUpdate) => [GUILD_INTEGRATIONS],
+    GuildScheduledEventCreate(Box<GuildScheduledEventCreate>) => [GUILD_SCHEDULED_EVENTS],
+    GuildScheduledEventDelete(Box<GuildScheduledEventDelete>) => [GUILD_SCHEDULED_EVENTS],
+    GuildScheduledEventUpdate(Box<GuildScheduledEventUpdate>) => [GUILD_SCHEDULED_EVENTS],
+    GuildScheduledEventUserAdd(GuildScheduledEventUserAdd) => [GUILD_SCHEDULED_EVENTS],
+    GuildScheduledEventUserRemove(GuildScheduledEventUserRemove) => [GUILD_SCHEDULED_EVENTS],
+    GuildStickersUpdate(GuildStickersUpdate) => [GUILD_EMOJIS_AND_STICKERS],
+    GuildUpdate(Box<GuildUpdate>) => [GUILDS],
+    IntegrationCreate(Box<IntegrationCreate>) => [GUILD_INTEGRATIONS],
+    IntegrationDelete(IntegrationDelete) => [GUILD_INTEGRATIONS],
+    IntegrationUpdate(Box<IntegrationUpdate>) => [GUILD_INTEGRATIONS],
+    InteractionCreate(Box<InteractionCreate>) => [],
+    InviteCreate(Box<InviteCreate>) => [GUILD_INVITES],
+    InviteDelete(InviteDelete) => [GUILD_INVITES],
+    MemberAdd(Box<MemberAdd>) => [GUILD_MEMBERS],
+    MemberChunk(MemberChunk) => [],
+    MemberRemove(MemberRemove) => [GUILD_MEMBERS],
+    MemberUpdate(Box<MemberUpdate>) => [GUILD_MEMBERS],
+    MessageCreate(Box<MessageCreate>) => [GUILD_MESSAGES, DIRECT_MESSAGES],
+    MessageDelete(MessageDelete) => [GUILD_MESSAGES, DIRECT_MESSAGES],
+    MessageDeleteBulk(MessageDeleteBulk) => [GUILD_MESSAGES],
+    MessagePollVoteAdd(MessagePollVoteAdd) => [GUILD_MESSAGE_POLLS, DIRECT_MESSAGE_POLLS],
+    MessagePollVoteRemove(MessagePollVoteRemove) => [GUILD_MESSAGE_POLLS, DIRECT_MESSAGE_POLLS],
+    MessageUpdate(Box<MessageUpdate>) => [GUILD_MESSAGES, DIRECT_MESSAGES],
+    PresenceUpdate(Box<PresenceUpdate>) => [GUILD_PRESENCES],
+    ReactionAdd(Box<ReactionAdd>) => [GUILD_MESSAGE_REACTIONS, DIRECT_MESSAGE_REACTIONS],
+    ReactionRemove(Box<ReactionRemove>) => [GUILD_MESSAGE_REACTIONS, DIRECT_MESSAGE_REACTIONS],
+    ReactionRemoveAll(ReactionRemoveAll) => [GUILD_MESSAGE_REACTIONS, DIRECT_MESSAGE_REACTIONS],
+    ReactionRemoveEmoji(ReactionRemoveEmoji) => [GUILD_MESSAGE_REACTIONS, DIRECT_MESSAGE_REACTIONS],
+    Ready(Box<Ready>) => [],
+    RoleCreate(RoleCreate) => [GUILDS],
+    RoleDelete(RoleDelete) => [GUILDS],
+    RoleUpdate(RoleUpdate) => [GUILDS],
+    StageInstanceCreate(StageInstanceCreate) => [GUILDS],
+    StageInstanceDelete(StageInstanceDelete) => [GUILDS],
+    StageInstanceUpdate(StageInstanceUpdate) => [GUILDS],
+    ThreadCreate(Box<ThreadCreate>) => [GUILDS],
+    ThreadDelete(ThreadDelete) => [GUILDS],
+    ThreadListSync(ThreadListSync) => [GUILDS],
+    ThreadMemberUpdate(Box<ThreadMemberUpdate>) => [GUILDS],
+    ThreadMembersUpdate(ThreadMembersUpdate) => [GUILDS, GUILD_MEMBERS],
+    ThreadUpdate(Box<ThreadUpdate>) => [GUILDS],
+    TypingStart(Box<TypingStart>) => [GUILD_MESSAGE_TYPING, DIRECT_MESSAGE_TYPING],
+    UnavailableGuild(UnavailableGuild) => [GUILDS],
+    UserUpdate(UserUpdate) => [],
+    VoiceServerUpdate(VoiceServerUpdate) => [],
+    VoiceStateUpdate(Box<VoiceStateUpdate>) => [GUILD_VOICE_STATES],
+    WebhooksUpdate(WebhooksUpdate) => [GUILD_WEBHOOKS],
 }
 
 #[async_trait]
 pub trait EventExt {
     async fn dispatch(self, context: Context, event_handler: &dyn EventHandler);
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use twilight_gateway::{Event, EventTypeFlags, Intents, ShardId};
+    use twilight_http::Client as HttpClient;
+    use twilight_model::{
+        gateway::payload::incoming::{RoleDelete, UnavailableGuild},
+        id::Id,
+    };
+
+    use crate::{
+        context::Context,
+        event_handler::{EventExt as _, EventHandler, event_intents},
+    };
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Mutex<Vec<&'static str>>,
+    }
+
+    #[async_trait]
+    impl EventHandler for Recorder {
+        async fn role_delete(&self, _context: Context, _role_delete: RoleDelete) {
+            self.events.lock().unwrap().push("role_delete");
+        }
+
+        async fn unavailable_guild(&self, _context: Context, _guild: UnavailableGuild) {
+            self.events.lock().unwrap().push("unavailable_guild");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_to_event_method() {
+        let context = Context::new(
+            Arc::new(HttpClient::new(String::new())),
+            None,
+            Arc::default(),
+            Arc::default(),
+            Arc::default(),
+            Arc::default(),
+            ShardId::ONE,
+        );
+        let recorder = Recorder::default();
+
+        Event::RoleDelete(RoleDelete {
+            guild_id: Id::new(1),
+            role_id: Id::new(1),
+        })
+        .dispatch(context.clone(), &recorder)
+        .await;
+        Event::GatewayHeartbeatAck
+            .dispatch(context.clone(), &recorder)
+            .await;
+        Event::UnavailableGuild(UnavailableGuild { id: Id::new(1) })
+            .dispatch(context, &recorder)
+            .await;
+
+        assert_eq!(
+            *recorder.events.lock().unwrap(),
+            ["role_delete", "unavailable_guild"]
+        );
+    }
+
+    #[test]
+    fn test_event_intents() {
+        assert_eq!(
+            event_intents::MESSAGE_CREATE,
+            Intents::GUILD_MESSAGES | Intents::DIRECT_MESSAGES
+        );
+        assert_eq!(event_intents::READY, Intents::empty());
+        assert_eq!(Recorder::default().required_intents(), Intents::empty());
+        assert!(
+            Recorder::default()
+                .used_event_flags()
+                .contains(EventTypeFlags::ROLE_DELETE | EventTypeFlags::MESSAGE_CREATE)
+        );
+    }
+}
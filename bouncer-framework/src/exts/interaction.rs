@@ -1,38 +1,779 @@
-use twilight_http::Client as HttpClient;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use tokio::sync::Mutex as AsyncMutex;
 use twilight_model::{
-    application::interaction::Interaction, http::interaction::InteractionResponse,
+    application::{command::CommandOptionChoice, interaction::Interaction},
+    channel::{
+        Message,
+        message::{
+            AllowedMentions, Component, Embed, MessageFlags,
+            component::{ActionRow, TextInput},
+        },
+    },
+    http::{
+        attachment::Attachment,
+        interaction::{InteractionResponse, InteractionResponseData, InteractionResponseType},
+    },
+    id::{Id, marker::InteractionMarker},
 };
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use crate::Context;
 
+/// Responding to interactions.
+///
+/// An interaction must be responded to within three seconds, either with a
+/// message, a modal, or by deferring the response. The original response can
+/// then be edited and follow-up messages sent for fifteen minutes.
+///
+/// Interactions handled by the [`CommandRegistry`] are tracked, so replying
+/// after the response was deferred edits the original response, and
+/// replying after it was responded to sends a follow-up message. As the
+/// visibility of the original response can't change, a reply that isn't as
+/// ephemeral as the deferral replaces the loading message with a follow-up.
+///
+/// [`CommandRegistry`]: crate::command::CommandRegistry
 #[async_trait::async_trait]
 pub trait InteractionExt {
-    async fn test(
+    /// Responds with a message.
+    async fn reply(
+        &self,
+        context: &Context,
+        message: impl Into<InteractionMessage> + Send,
+    ) -> Result<(), InteractionExtError>;
+
+    /// Responds with a message only the user who triggered the interaction
+    /// can see.
+    async fn reply_ephemeral(
+        &self,
+        context: &Context,
+        message: impl Into<InteractionMessage> + Send,
+    ) -> Result<(), InteractionExtError>;
+
+    /// Acknowledges the interaction, showing a loading state until the
+    /// original response is edited.
+    async fn defer(&self, context: &Context) -> Result<(), InteractionExtError>;
+
+    /// Acknowledges the interaction like [`InteractionExt::defer`], with a
+    /// response only the user who triggered the interaction can see.
+    async fn defer_ephemeral(&self, context: &Context) -> Result<(), InteractionExtError>;
+
+    /// Acknowledges a message component interaction without a loading state,
+    /// to edit the message the component is attached to later with
+    /// [`InteractionExt::edit_original`].
+    ///
+    /// Replies sent afterwards are follow-up messages.
+    async fn defer_update(&self, context: &Context) -> Result<(), InteractionExtError>;
+
+    /// Edits the original response, leaving out whatever the message doesn't
+    /// set.
+    async fn edit_original(
         &self,
-        http_client: &HttpClient,
-        response: InteractionResponse,
+        context: &Context,
+        message: impl Into<InteractionMessage> + Send,
+    ) -> Result<Message, InteractionExtError>;
+
+    /// Sends a message after the interaction has been responded to.
+    async fn followup(
+        &self,
+        context: &Context,
+        message: impl Into<InteractionMessage> + Send,
+    ) -> Result<Message, InteractionExtError>;
+
+    async fn delete_original(&self, context: &Context) -> Result<(), InteractionExtError>;
+
+    /// Responds to a message component interaction by editing the message the
+    /// component is attached to.
+    async fn update_message(
+        &self,
+        context: &Context,
+        message: impl Into<InteractionMessage> + Send,
+    ) -> Result<(), InteractionExtError>;
+
+    /// Responds with a modal for the user to fill in.
+    async fn show_modal(
+        &self,
+        context: &Context,
+        modal: InteractionModal,
+    ) -> Result<(), InteractionExtError>;
+
+    /// Responds to an autocomplete interaction with the choices to suggest.
+    async fn autocomplete(
+        &self,
+        context: &Context,
+        choices: Vec<CommandOptionChoice>,
     ) -> Result<(), InteractionExtError>;
+
+    /// Picks the string for the locale of the user who triggered the
+    /// interaction, falling back to the guild's locale and then to `default`.
+    ///
+    /// `localizations` are `(locale, string)` pairs, such as `("de", "Hallo")`.
+    #[must_use]
+    fn localize<'a>(&self, localizations: &[(&str, &'a str)], default: &'a str) -> &'a str;
 }
 
-// TODO: Custom `response` type for messages.
 #[async_trait::async_trait]
 impl InteractionExt for Interaction {
-    async fn test(
+    async fn reply(
         &self,
-        http_client: &HttpClient,
-        response: InteractionResponse,
+        context: &Context,
+        message: impl Into<InteractionMessage> + Send,
     ) -> Result<(), InteractionExtError> {
-        let application_id = self.application_id;
+        let message = message.into();
+        let Some(state) = context.interaction_responses.state(self.id) else {
+            return create_response(self, context, &message.response()).await;
+        };
+
+        let mut state = state.lock().await;
+        match *state {
+            ResponseState::Pending => create_response(self, context, &message.response()).await?,
+            ResponseState::Deferred { ephemeral } if ephemeral == message.is_ephemeral() => {
+                update_response(self, context, &message).await?;
+            }
+            ResponseState::Deferred { .. } => {
+                // A follow-up sent while the loading message is shown would
+                // edit it instead, keeping the deferral's visibility.
+                self.delete_original(context).await?;
+                create_followup(self, context, &message).await?;
+            }
+            ResponseState::Responded => {
+                create_followup(self, context, &message).await?;
+            }
+        }
+        *state = ResponseState::Responded;
+        drop(state);
+
+        Ok(())
+    }
+
+    async fn reply_ephemeral(
+        &self,
+        context: &Context,
+        message: impl Into<InteractionMessage> + Send,
+    ) -> Result<(), InteractionExtError> {
+        self.reply(context, message.into().ephemeral()).await
+    }
+
+    async fn defer(&self, context: &Context) -> Result<(), InteractionExtError> {
+        let response = InteractionResponse {
+            kind: InteractionResponseType::DeferredChannelMessageWithSource,
+            data: None,
+        };
+
+        defer_response(
+            self,
+            context,
+            &response,
+            ResponseState::Deferred { ephemeral: false },
+        )
+        .await
+    }
+
+    async fn defer_ephemeral(&self, context: &Context) -> Result<(), InteractionExtError> {
+        let response = InteractionResponse {
+            kind: InteractionResponseType::DeferredChannelMessageWithSource,
+            data: Some(
+                InteractionResponseDataBuilder::new()
+                    .flags(MessageFlags::EPHEMERAL)
+                    .build(),
+            ),
+        };
+
+        defer_response(
+            self,
+            context,
+            &response,
+            ResponseState::Deferred { ephemeral: true },
+        )
+        .await
+    }
+
+    async fn defer_update(&self, context: &Context) -> Result<(), InteractionExtError> {
+        let response = InteractionResponse {
+            kind: InteractionResponseType::DeferredUpdateMessage,
+            data: None,
+        };
+
+        defer_response(self, context, &response, ResponseState::Responded).await
+    }
+
+    async fn edit_original(
+        &self,
+        context: &Context,
+        message: impl Into<InteractionMessage> + Send,
+    ) -> Result<Message, InteractionExtError> {
+        let message = message.into();
+        let Some(state) = context.interaction_responses.state(self.id) else {
+            return update_response(self, context, &message).await;
+        };
+
+        let mut state = state.lock().await;
+        let message = update_response(self, context, &message).await?;
+        *state = ResponseState::Responded;
+        drop(state);
+
+        Ok(message)
+    }
+
+    async fn followup(
+        &self,
+        context: &Context,
+        message: impl Into<InteractionMessage> + Send,
+    ) -> Result<Message, InteractionExtError> {
+        create_followup(self, context, &message.into()).await
+    }
 
-        http_client
-            .interaction(application_id)
-            .create_response(self.id, &self.token, &response)
+    async fn delete_original(&self, context: &Context) -> Result<(), InteractionExtError> {
+        context
+            .http
+            .interaction(self.application_id)
+            .delete_response(&self.token)
             .await?;
 
         Ok(())
     }
+
+    async fn update_message(
+        &self,
+        context: &Context,
+        message: impl Into<InteractionMessage> + Send,
+    ) -> Result<(), InteractionExtError> {
+        let response = InteractionResponse {
+            kind: InteractionResponseType::UpdateMessage,
+            data: Some(message.into().response_data()),
+        };
+
+        create_tracked_response(self, context, &response).await
+    }
+
+    async fn show_modal(
+        &self,
+        context: &Context,
+        modal: InteractionModal,
+    ) -> Result<(), InteractionExtError> {
+        let response = InteractionResponse {
+            kind: InteractionResponseType::Modal,
+            data: Some(
+                InteractionResponseDataBuilder::new()
+                    .custom_id(modal.custom_id)
+                    .title(modal.title)
+                    .components(modal.components)
+                    .build(),
+            ),
+        };
+
+        create_tracked_response(self, context, &response).await
+    }
+
+    async fn autocomplete(
+        &self,
+        context: &Context,
+        choices: Vec<CommandOptionChoice>,
+    ) -> Result<(), InteractionExtError> {
+        let response = InteractionResponse {
+            kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+            data: Some(
+                InteractionResponseDataBuilder::new()
+                    .choices(choices)
+                    .build(),
+            ),
+        };
+
+        create_tracked_response(self, context, &response).await
+    }
+
+    fn localize<'a>(&self, localizations: &[(&str, &'a str)], default: &'a str) -> &'a str {
+        [&self.locale, &self.guild_locale]
+            .into_iter()
+            .flatten()
+            .find_map(|locale| {
+                localizations
+                    .iter()
+                    .find(|&&(localization_locale, _)| localization_locale == locale)
+                    .map(|&(_, string)| string)
+            })
+            .unwrap_or(default)
+    }
+}
+
+async fn create_response(
+    interaction: &Interaction,
+    context: &Context,
+    response: &InteractionResponse,
+) -> Result<(), InteractionExtError> {
+    context
+        .http
+        .interaction(interaction.application_id)
+        .create_response(interaction.id, &interaction.token, response)
+        .await?;
+
+    Ok(())
+}
+
+/// Creates a response, marking the interaction as responded to if it's
+/// tracked.
+async fn create_tracked_response(
+    interaction: &Interaction,
+    context: &Context,
+    response: &InteractionResponse,
+) -> Result<(), InteractionExtError> {
+    let Some(state) = context.interaction_responses.state(interaction.id) else {
+        return create_response(interaction, context, response).await;
+    };
+
+    let mut state = state.lock().await;
+    create_response(interaction, context, response).await?;
+    *state = ResponseState::Responded;
+    drop(state);
+
+    Ok(())
+}
+
+/// Sends a deferred response, moving a tracked interaction to
+/// `deferred_state`, unless it was already deferred or responded to.
+async fn defer_response(
+    interaction: &Interaction,
+    context: &Context,
+    response: &InteractionResponse,
+    deferred_state: ResponseState,
+) -> Result<(), InteractionExtError> {
+    let Some(state) = context.interaction_responses.state(interaction.id) else {
+        return create_response(interaction, context, response).await;
+    };
+
+    let mut state = state.lock().await;
+    if *state == ResponseState::Pending {
+        create_response(interaction, context, response).await?;
+        *state = deferred_state;
+    }
+    drop(state);
+
+    Ok(())
+}
+
+async fn update_response(
+    interaction: &Interaction,
+    context: &Context,
+    message: &InteractionMessage,
+) -> Result<Message, InteractionExtError> {
+    let interaction_client = context.http.interaction(interaction.application_id);
+
+    let mut request = interaction_client.update_response(&interaction.token);
+    if message.content.is_some() {
+        request = request.content(message.content.as_deref());
+    }
+    if message.embeds.is_some() {
+        request = request.embeds(message.embeds.as_deref());
+    }
+    if message.components.is_some() {
+        request = request.components(message.components.as_deref());
+    }
+    if message.allowed_mentions.is_some() {
+        request = request.allowed_mentions(message.allowed_mentions.as_ref());
+    }
+    if let Some(attachments) = &message.attachments {
+        request = request.attachments(attachments);
+    }
+
+    Ok(request.await?.model().await?)
+}
+
+async fn create_followup(
+    interaction: &Interaction,
+    context: &Context,
+    message: &InteractionMessage,
+) -> Result<Message, InteractionExtError> {
+    let interaction_client = context.http.interaction(interaction.application_id);
+
+    let mut request = interaction_client
+        .create_followup(&interaction.token)
+        .allowed_mentions(message.allowed_mentions.as_ref());
+    if let Some(content) = &message.content {
+        request = request.content(content);
+    }
+    if let Some(embeds) = &message.embeds {
+        request = request.embeds(embeds);
+    }
+    if let Some(components) = &message.components {
+        request = request.components(components);
+    }
+    if let Some(attachments) = &message.attachments {
+        request = request.attachments(attachments);
+    }
+    if let Some(flags) = message.flags {
+        request = request.flags(flags);
+    }
+
+    Ok(request.await?.model().await?)
+}
+
+/// Whether an interaction has been responded to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseState {
+    Pending,
+    /// The response was deferred, and the next reply edits the original
+    /// response if it's as ephemeral as the deferral.
+    Deferred {
+        ephemeral: bool,
+    },
+    /// Any further reply is sent as a follow-up message.
+    Responded,
+}
+
+/// Tracks the response state of the interactions the framework is handling.
+///
+/// Replies to tracked interactions are turned into edits of the original
+/// response once it was deferred, and into follow-up messages once it was
+/// responded to.
+#[derive(Debug, Default)]
+pub struct InteractionResponses {
+    states: Mutex<HashMap<Id<InteractionMarker>, Arc<AsyncMutex<ResponseState>>>>,
+}
+
+impl InteractionResponses {
+    /// Starts tracking an interaction that hasn't been responded to yet,
+    /// until the returned guard is dropped.
+    pub fn track(&self, interaction_id: Id<InteractionMarker>) -> TrackedInteraction<'_> {
+        self.lock_states().insert(
+            interaction_id,
+            Arc::new(AsyncMutex::new(ResponseState::Pending)),
+        );
+
+        TrackedInteraction {
+            responses: self,
+            interaction_id,
+        }
+    }
+
+    /// Returns the response state of an interaction, or `None` if it isn't
+    /// tracked.
+    pub async fn response_state(
+        &self,
+        interaction_id: Id<InteractionMarker>,
+    ) -> Option<ResponseState> {
+        let state = self.state(interaction_id)?;
+        let state = *state.lock().await;
+
+        Some(state)
+    }
+
+    fn state(
+        &self,
+        interaction_id: Id<InteractionMarker>,
+    ) -> Option<Arc<AsyncMutex<ResponseState>>> {
+        self.lock_states().get(&interaction_id).cloned()
+    }
+
+    fn lock_states(
+        &self,
+    ) -> MutexGuard<'_, HashMap<Id<InteractionMarker>, Arc<AsyncMutex<ResponseState>>>> {
+        self.states.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Stops tracking an interaction when dropped, even if its handler panicked.
+#[must_use = "the interaction is untracked when the guard is dropped"]
+#[derive(Debug)]
+pub struct TrackedInteraction<'a> {
+    responses: &'a InteractionResponses,
+    interaction_id: Id<InteractionMarker>,
+}
+
+impl Drop for TrackedInteraction<'_> {
+    fn drop(&mut self) {
+        self.responses.lock_states().remove(&self.interaction_id);
+    }
+}
+
+/// A message to respond to an interaction with.
+///
+/// Anything left unset is left out of the message, or left unchanged when
+/// editing the original response.
+#[derive(Debug, Clone, Default)]
+#[must_use]
+pub struct InteractionMessage {
+    content: Option<String>,
+    embeds: Option<Vec<Embed>>,
+    components: Option<Vec<Component>>,
+    allowed_mentions: Option<AllowedMentions>,
+    attachments: Option<Vec<Attachment>>,
+    flags: Option<MessageFlags>,
+}
+
+impl InteractionMessage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn content(mut self, content: impl Into<String>) -> Self {
+        self.content = Some(content.into());
+        self
+    }
+
+    pub fn embed(mut self, embed: Embed) -> Self {
+        self.embeds.get_or_insert_with(Vec::new).push(embed);
+        self
+    }
+
+    /// Sets the message's embeds, replacing any set before.
+    pub fn embeds(mut self, embeds: impl IntoIterator<Item = Embed>) -> Self {
+        self.embeds = Some(embeds.into_iter().collect());
+        self
+    }
+
+    pub fn component(mut self, component: Component) -> Self {
+        self.components.get_or_insert_with(Vec::new).push(component);
+        self
+    }
+
+    /// Sets the message's components, replacing any set before.
+    pub fn components(mut self, components: impl IntoIterator<Item = Component>) -> Self {
+        self.components = Some(components.into_iter().collect());
+        self
+    }
+
+    pub fn allowed_mentions(mut self, allowed_mentions: AllowedMentions) -> Self {
+        self.allowed_mentions = Some(allowed_mentions);
+        self
+    }
+
+    pub fn attachment(mut self, attachment: Attachment) -> Self {
+        self.attachments
+            .get_or_insert_with(Vec::new)
+            .push(attachment);
+        self
+    }
+
+    /// Sets the message's attachments, replacing any set before.
+    pub fn attachments(mut self, attachments: impl IntoIterator<Item = Attachment>) -> Self {
+        self.attachments = Some(attachments.into_iter().collect());
+        self
+    }
+
+    /// Makes the message visible only to the user who triggered the
+    /// interaction.
+    ///
+    /// This has no effect when editing the original response, whose
+    /// visibility is set by the response or deferral that created it.
+    pub const fn ephemeral(mut self) -> Self {
+        self.flags = Some(MessageFlags::EPHEMERAL);
+        self
+    }
+
+    fn is_ephemeral(&self) -> bool {
+        self.flags
+            .is_some_and(|flags| flags.contains(MessageFlags::EPHEMERAL))
+    }
+
+    fn response(self) -> InteractionResponse {
+        InteractionResponse {
+            kind: InteractionResponseType::ChannelMessageWithSource,
+            data: Some(self.response_data()),
+        }
+    }
+
+    fn response_data(self) -> InteractionResponseData {
+        let mut builder = InteractionResponseDataBuilder::new();
+        if let Some(content) = self.content {
+            builder = builder.content(content);
+        }
+        if let Some(embeds) = self.embeds {
+            builder = builder.embeds(embeds);
+        }
+        if let Some(components) = self.components {
+            builder = builder.components(components);
+        }
+        if let Some(allowed_mentions) = self.allowed_mentions {
+            builder = builder.allowed_mentions(allowed_mentions);
+        }
+        if let Some(attachments) = self.attachments {
+            builder = builder.attachments(attachments);
+        }
+        if let Some(flags) = self.flags {
+            builder = builder.flags(flags);
+        }
+
+        builder.build()
+    }
+}
+
+impl From<&str> for InteractionMessage {
+    fn from(content: &str) -> Self {
+        Self::new().content(content)
+    }
+}
+
+impl From<String> for InteractionMessage {
+    fn from(content: String) -> Self {
+        Self::new().content(content)
+    }
+}
+
+impl From<Embed> for InteractionMessage {
+    fn from(embed: Embed) -> Self {
+        Self::new().embed(embed)
+    }
+}
+
+/// A modal to respond to an interaction with, made of text inputs.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct InteractionModal {
+    custom_id: String,
+    title: String,
+    components: Vec<Component>,
+}
+
+impl InteractionModal {
+    pub fn new(custom_id: impl Into<String>, title: impl Into<String>) -> Self {
+        Self {
+            custom_id: custom_id.into(),
+            title: title.into(),
+            components: Vec::new(),
+        }
+    }
+
+    /// Adds a text input on its own row.
+    pub fn text_input(mut self, text_input: TextInput) -> Self {
+        self.components.push(Component::ActionRow(ActionRow {
+            components: vec![Component::TextInput(text_input)],
+        }));
+        self
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum InteractionExtError {
     #[error(transparent)]
     HttpError(#[from] twilight_http::Error),
+    #[error(transparent)]
+    DeserializeBodyError(#[from] twilight_http::response::DeserializeBodyError),
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::{
+        application::interaction::{Interaction, InteractionType},
+        channel::message::{
+            Component, MessageFlags,
+            component::{TextInput, TextInputStyle},
+        },
+        http::interaction::InteractionResponseType,
+        id::Id,
+        oauth::ApplicationIntegrationMap,
+    };
+
+    use crate::exts::interaction::{
+        InteractionExt as _, InteractionMessage, InteractionModal, InteractionResponses,
+        ResponseState,
+    };
+
+    #[allow(deprecated)]
+    fn interaction(locale: Option<&str>, guild_locale: Option<&str>) -> Interaction {
+        Interaction {
+            app_permissions: None,
+            application_id: Id::new(1),
+            authorizing_integration_owners: ApplicationIntegrationMap {
+                guild: None,
+                user: None,
+            },
+            channel: None,
+            channel_id: None,
+            context: None,
+            data: None,
+            entitlements: Vec::new(),
+            guild: None,
+            guild_id: None,
+            guild_locale: guild_locale.map(String::from),
+            id: Id::new(1),
+            kind: InteractionType::ApplicationCommand,
+            locale: locale.map(String::from),
+            member: None,
+            message: None,
+            token: String::new(),
+            user: None,
+        }
+    }
+
+    #[test]
+    fn test_localize() {
+        let localizations = [("de", "Hallo"), ("fr", "Bonjour")];
+
+        assert_eq!(
+            interaction(Some("de"), Some("fr")).localize(&localizations, "Hello"),
+            "Hallo"
+        );
+        assert_eq!(
+            interaction(Some("ja"), Some("fr")).localize(&localizations, "Hello"),
+            "Bonjour"
+        );
+        assert_eq!(
+            interaction(Some("ja"), None).localize(&localizations, "Hello"),
+            "Hello"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_track_interaction() {
+        let responses = InteractionResponses::default();
+
+        let tracked = responses.track(Id::new(1));
+        assert_eq!(
+            responses.response_state(Id::new(1)).await,
+            Some(ResponseState::Pending)
+        );
+        assert_eq!(responses.response_state(Id::new(2)).await, None);
+
+        drop(tracked);
+        assert_eq!(responses.response_state(Id::new(1)).await, None);
+    }
+
+    #[test]
+    fn test_message_response() {
+        let response = InteractionMessage::from("meow").response();
+        let data = response.data.unwrap();
+
+        assert_eq!(
+            response.kind,
+            InteractionResponseType::ChannelMessageWithSource
+        );
+        assert_eq!(data.content.as_deref(), Some("meow"));
+        assert_eq!(data.flags, None);
+        assert_eq!(data.embeds, None);
+    }
+
+    #[test]
+    fn test_ephemeral_message() {
+        let message = InteractionMessage::new().content("meow").ephemeral();
+        assert!(message.is_ephemeral());
+
+        let data = message.response_data();
+        assert_eq!(data.flags, Some(MessageFlags::EPHEMERAL));
+        assert!(!InteractionMessage::new().is_ephemeral());
+    }
+
+    #[test]
+    fn test_modal_text_inputs() {
+        let text_input = TextInput {
+            custom_id: String::from("reason"),
+            label: String::from("Reason"),
+            max_length: None,
+            min_length: None,
+            placeholder: None,
+            required: None,
+            style: TextInputStyle::Paragraph,
+            value: None,
+        };
+        let modal = InteractionModal::new("ban", "Ban")
+            .text_input(text_input.clone())
+            .text_input(text_input);
+
+        assert_eq!(modal.components.len(), 2);
+        assert!(modal.components.iter().all(
+            |component| matches!(component, Component::ActionRow(row) if row.components.len() == 1)
+        ));
+    }
 }
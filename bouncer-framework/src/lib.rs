@@ -1,8 +1,17 @@
+pub mod cache;
 pub mod client;
+pub mod collector;
 pub mod command;
+pub mod component;
 pub mod context;
+pub mod data;
+pub mod dispatcher;
 pub mod event_handler;
 pub mod exts;
+pub mod middleware;
+pub mod session;
+pub mod shard;
+pub mod shutdown;
 
 pub use client::Client;
 pub use context::Context;
@@ -0,0 +1,173 @@
+use async_trait::async_trait;
+use twilight_gateway::Event;
+
+use crate::{
+    context::Context,
+    event_handler::{EventExt as _, EventHandler},
+};
+
+/// Wraps the dispatch of every event to the event handlers.
+///
+/// Middleware runs in the order it's added, each deciding whether to pass the
+/// event on by running `next`, which lets it time, trace or guard the handlers
+/// after it, or drop the event altogether.
+#[async_trait]
+pub trait Middleware: Send + Sync {
+    async fn handle(&self, context: Context, event: Event, next: Next<'_>);
+}
+
+/// The rest of the middleware chain, ending with the event handlers.
+#[derive(Clone, Copy)]
+pub struct Next<'a> {
+    middlewares: &'a [Box<dyn Middleware>],
+    event_handlers: &'a [Box<dyn EventHandler>],
+}
+
+impl<'a> Next<'a> {
+    pub(crate) const fn new(
+        middlewares: &'a [Box<dyn Middleware>],
+        event_handlers: &'a [Box<dyn EventHandler>],
+    ) -> Self {
+        Self {
+            middlewares,
+            event_handlers,
+        }
+    }
+
+    /// Passes the event to the next middleware, or once there's none left, to
+    /// each event handler in turn.
+    pub async fn run(self, context: Context, event: Event) {
+        if let Some((middleware, middlewares)) = self.middlewares.split_first() {
+            let next = Self::new(middlewares, self.event_handlers);
+            middleware.handle(context, event, next).await;
+
+            return;
+        }
+
+        for event_handler in self.event_handlers {
+            event
+                .clone()
+                .dispatch(context.clone(), &**event_handler)
+                .await;
+        }
+    }
+}
+
+impl core::fmt::Debug for Next<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Next")
+            .field("middlewares", &self.middlewares.len())
+            .field("event_handlers", &self.event_handlers.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use twilight_gateway::{Event, ShardId};
+    use twilight_http::Client as HttpClient;
+    use twilight_model::{gateway::payload::incoming::RoleDelete, id::Id};
+
+    use crate::{
+        context::Context,
+        event_handler::EventHandler,
+        middleware::{Middleware, Next},
+    };
+
+    type Log = Arc<Mutex<Vec<&'static str>>>;
+
+    struct Logger {
+        name: &'static str,
+        log: Log,
+    }
+
+    #[async_trait]
+    impl EventHandler for Logger {
+        async fn role_delete(&self, _context: Context, _role_delete: RoleDelete) {
+            self.log.lock().unwrap().push(self.name);
+        }
+    }
+
+    /// Logs around the rest of the chain, unless it drops the event.
+    struct Wrapper {
+        name: &'static str,
+        drops: bool,
+        log: Log,
+    }
+
+    #[async_trait]
+    impl Middleware for Wrapper {
+        async fn handle(&self, context: Context, event: Event, next: Next<'_>) {
+            self.log.lock().unwrap().push(self.name);
+            if !self.drops {
+                next.run(context, event).await;
+            }
+        }
+    }
+
+    fn context() -> Context {
+        Context::new(
+            Arc::new(HttpClient::new(String::new())),
+            None,
+            Arc::default(),
+            Arc::default(),
+            Arc::default(),
+            Arc::default(),
+            ShardId::ONE,
+        )
+    }
+
+    fn event() -> Event {
+        Event::RoleDelete(RoleDelete {
+            guild_id: Id::new(1),
+            role_id: Id::new(1),
+        })
+    }
+
+    fn wrapper(name: &'static str, drops: bool, log: &Log) -> Box<dyn Middleware> {
+        Box::new(Wrapper {
+            name,
+            drops,
+            log: Arc::clone(log),
+        })
+    }
+
+    fn logger(name: &'static str, log: &Log) -> Box<dyn EventHandler> {
+        Box::new(Logger {
+            name,
+            log: Arc::clone(log),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_run_in_order() {
+        let log = Log::default();
+        let middlewares = [
+            wrapper("first", false, &log),
+            wrapper("second", false, &log),
+        ];
+        let event_handlers = [logger("a", &log), logger("b", &log)];
+
+        Next::new(&middlewares, &event_handlers)
+            .run(context(), event())
+            .await;
+
+        assert_eq!(*log.lock().unwrap(), ["first", "second", "a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn test_drop_events() {
+        let log = Log::default();
+        let middlewares = [wrapper("first", true, &log), wrapper("second", false, &log)];
+        let event_handlers = [logger("a", &log)];
+
+        Next::new(&middlewares, &event_handlers)
+            .run(context(), event())
+            .await;
+
+        assert_eq!(*log.lock().unwrap(), ["first"]);
+    }
+}
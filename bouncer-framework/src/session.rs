@@ -0,0 +1,96 @@
+use std::{io::ErrorKind, path::PathBuf};
+
+use async_trait::async_trait;
+use twilight_gateway::{Session, Shard, ShardId};
+
+/// The gateway session of a shard, for resuming it after a restart.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ShardSession {
+    pub shard_id: ShardId,
+    pub session: Session,
+    pub resume_url: Option<String>,
+}
+
+impl ShardSession {
+    /// Takes the session of a shard, if it has one to resume.
+    #[must_use]
+    pub fn from_shard(shard: &Shard) -> Option<Self> {
+        Some(Self {
+            shard_id: shard.id(),
+            session: shard.session()?.clone(),
+            resume_url: shard.resume_url().map(ToOwned::to_owned),
+        })
+    }
+}
+
+/// Persists the shards' sessions across restarts.
+///
+/// Sessions are saved once the client has shut down, and loaded when it
+/// starts. A shard whose session Discord no longer accepts identifies anew.
+#[async_trait]
+pub trait SessionStore: Send + Sync {
+    /// # Errors
+    ///
+    /// Returns an error if the sessions couldn't be loaded, in which case
+    /// every shard identifies anew.
+    async fn load(&self) -> anyhow::Result<Vec<ShardSession>>;
+
+    /// # Errors
+    ///
+    /// Returns an error if the sessions couldn't be saved.
+    async fn save(&self, sessions: Vec<ShardSession>) -> anyhow::Result<()>;
+}
+
+/// Stores the sessions in a JSON file.
+#[derive(Debug, Clone)]
+pub struct FileSessionStore {
+    path: PathBuf,
+}
+
+impl FileSessionStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl SessionStore for FileSessionStore {
+    async fn load(&self) -> anyhow::Result<Vec<ShardSession>> {
+        match tokio::fs::read(&self.path).await {
+            Ok(sessions) => Ok(serde_json::from_slice(&sessions)?),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    async fn save(&self, sessions: Vec<ShardSession>) -> anyhow::Result<()> {
+        tokio::fs::write(&self.path, serde_json::to_vec(&sessions)?).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_gateway::{Session, ShardId};
+
+    use crate::session::{FileSessionStore, SessionStore as _, ShardSession};
+
+    #[tokio::test]
+    async fn test_file_session_store() {
+        let path =
+            std::env::temp_dir().join(format!("bouncer-sessions-{}.json", std::process::id()));
+        let store = FileSessionStore::new(&path);
+        let sessions = vec![ShardSession {
+            shard_id: ShardId::new(1, 2),
+            session: Session::new(42, "session".to_owned()),
+            resume_url: Some("wss://gateway.discord.gg".to_owned()),
+        }];
+
+        assert!(store.load().await.unwrap().is_empty());
+        store.save(sessions.clone()).await.unwrap();
+        assert_eq!(store.load().await.unwrap(), sessions);
+
+        tokio::fs::remove_file(&path).await.unwrap();
+    }
+}
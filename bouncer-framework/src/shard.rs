@@ -0,0 +1,130 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use core::time::Duration;
+
+use twilight_gateway::{Event, Shard, ShardState};
+
+/// A shard's connection, as of the last event it received.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShardStatus {
+    pub state: ShardState,
+    /// The average heartbeat latency, once a heartbeat has been acknowledged.
+    pub latency: Option<Duration>,
+}
+
+/// A change in a shard's connection to the gateway.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShardLifecycle {
+    /// The connection was closed, with the close code if there was one.
+    ///
+    /// The shard reconnects unless the close code is fatal or the client is
+    /// shutting down.
+    Disconnected { close_code: Option<u16> },
+    /// Reconnecting failed, and is retried after a backoff growing with each
+    /// attempt.
+    Reconnecting { attempt: u8 },
+    /// The session was resumed after reconnecting, so no events were missed.
+    Resumed,
+}
+
+impl ShardLifecycle {
+    pub(crate) fn from_event(event: &Event) -> Option<Self> {
+        match event {
+            Event::GatewayClose(close_frame) => Some(Self::Disconnected {
+                close_code: close_frame.as_ref().map(|close_frame| close_frame.code),
+            }),
+            Event::Resumed => Some(Self::Resumed),
+            _ => None,
+        }
+    }
+}
+
+/// The status of each shard the client runs, by shard number.
+#[derive(Debug, Default)]
+pub struct ShardStatuses {
+    statuses: Mutex<BTreeMap<u32, ShardStatus>>,
+}
+
+impl ShardStatuses {
+    #[must_use]
+    pub fn get(&self, shard_number: u32) -> Option<ShardStatus> {
+        self.lock_statuses().get(&shard_number).copied()
+    }
+
+    #[must_use]
+    pub fn all(&self) -> BTreeMap<u32, ShardStatus> {
+        self.lock_statuses().clone()
+    }
+
+    /// The average latency of the shards with an acknowledged heartbeat.
+    #[must_use]
+    pub fn average_latency(&self) -> Option<Duration> {
+        let statuses = self.lock_statuses();
+        let latencies = statuses
+            .values()
+            .filter_map(|status| status.latency)
+            .collect::<Vec<_>>();
+        drop(statuses);
+
+        let count = u32::try_from(latencies.len())
+            .ok()
+            .filter(|&count| count > 0)?;
+
+        Some(latencies.into_iter().sum::<Duration>() / count)
+    }
+
+    pub(crate) fn update(&self, shard: &Shard) {
+        self.lock_statuses().insert(
+            shard.id().number(),
+            ShardStatus {
+                state: shard.state(),
+                latency: shard.latency().average(),
+            },
+        );
+    }
+
+    fn lock_statuses(&self) -> MutexGuard<'_, BTreeMap<u32, ShardStatus>> {
+        self.statuses.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_gateway::{CloseFrame, Event};
+
+    use crate::shard::{ShardLifecycle, ShardStatuses};
+
+    #[test]
+    fn test_lifecycle_from_event() {
+        assert_eq!(
+            ShardLifecycle::from_event(&Event::GatewayClose(Some(CloseFrame::new(4004, "")))),
+            Some(ShardLifecycle::Disconnected {
+                close_code: Some(4004)
+            })
+        );
+        assert_eq!(
+            ShardLifecycle::from_event(&Event::GatewayClose(None)),
+            Some(ShardLifecycle::Disconnected { close_code: None })
+        );
+        assert_eq!(
+            ShardLifecycle::from_event(&Event::Resumed),
+            Some(ShardLifecycle::Resumed)
+        );
+        assert_eq!(
+            ShardLifecycle::from_event(&Event::GatewayHeartbeatAck),
+            None
+        );
+    }
+
+    #[test]
+    fn test_no_statuses_before_events() {
+        let statuses = ShardStatuses::default();
+
+        assert!(statuses.get(0).is_none());
+        assert!(statuses.all().is_empty());
+        assert!(statuses.average_latency().is_none());
+    }
+}
@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use core::time::Duration;
+
+use tokio::sync::watch;
+
+/// How long shutting down waits for in-flight handlers by default.
+pub const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Shuts down a running client, from anywhere.
+#[derive(Debug, Clone)]
+pub struct ShutdownHandle {
+    sender: Arc<watch::Sender<bool>>,
+}
+
+impl ShutdownHandle {
+    pub(crate) fn new() -> (Self, watch::Receiver<bool>) {
+        let (sender, receiver) = watch::channel(false);
+
+        (
+            Self {
+                sender: Arc::new(sender),
+            },
+            receiver,
+        )
+    }
+
+    /// Closes the shards with a resumable close frame, after which
+    /// [`Client::start`](crate::Client::start) waits for the in-flight
+    /// handlers and returns.
+    pub fn shutdown(&self) {
+        self.sender.send_replace(true);
+    }
+
+    #[must_use]
+    pub fn is_shutdown(&self) -> bool {
+        *self.sender.borrow()
+    }
+}
+
+/// The number of events and interactions still being handled, for shutting
+/// down to wait on.
+#[derive(Debug)]
+pub(crate) struct InFlightTasks {
+    count: watch::Sender<usize>,
+}
+
+impl InFlightTasks {
+    /// Counts a task as in-flight until the returned guard is dropped.
+    pub(crate) fn start(self: &Arc<Self>) -> InFlightGuard {
+        self.count.send_modify(|count| *count += 1);
+
+        InFlightGuard {
+            tasks: Arc::clone(self),
+        }
+    }
+
+    /// Waits for every in-flight task to finish.
+    pub(crate) async fn wait(&self) {
+        let mut receiver = self.count.subscribe();
+        // The sender is borrowed, so this can't fail.
+        let _ = receiver.wait_for(|&count| count == 0).await;
+    }
+}
+
+impl Default for InFlightTasks {
+    fn default() -> Self {
+        Self {
+            count: watch::Sender::new(0),
+        }
+    }
+}
+
+/// Marks a task as in-flight until dropped, including when it panics.
+#[derive(Debug)]
+pub(crate) struct InFlightGuard {
+    tasks: Arc<InFlightTasks>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.tasks.count.send_modify(|count| *count -= 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use core::time::Duration;
+
+    use crate::shutdown::{InFlightTasks, ShutdownHandle};
+
+    #[test]
+    fn test_shutdown_handle() {
+        let (shutdown, receiver) = ShutdownHandle::new();
+        let clone = shutdown.clone();
+
+        assert!(!shutdown.is_shutdown());
+        clone.shutdown();
+        assert!(shutdown.is_shutdown());
+        assert!(*receiver.borrow());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_in_flight_tasks() {
+        let tasks = Arc::<InFlightTasks>::default();
+        let first = tasks.start();
+        let second = tasks.start();
+
+        drop(first);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(10), tasks.wait())
+                .await
+                .is_err()
+        );
+        drop(second);
+        tokio::time::timeout(Duration::from_secs(1), tasks.wait())
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,118 @@
+use quote::format_ident;
+
+/// `EventHandler` methods generated by `#[event_handler]`, rather than
+/// handling an event.
+const GENERATED_METHODS: &[&str] = &["used_event_flags", "required_intents"];
+/// `EventHandler` methods hooking into the client's lifecycle, rather than
+/// handling an event.
+const LIFECYCLE_METHODS: &[&str] = &["shard_lifecycle", "shutdown"];
+
+/// An `EventHandler` implementation, along with the events it handles.
+#[derive(Debug)]
+pub struct EventHandler {
+    item: syn::ItemImpl,
+    events: Vec<syn::Ident>,
+}
+
+impl EventHandler {
+    pub fn from_item_impl(item: syn::ItemImpl) -> darling::Result<Self> {
+        let mut errors = darling::Error::accumulator();
+        let events = item
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                syn::ImplItem::Fn(method) => Some(&method.sig.ident),
+                _ => None,
+            })
+            .filter(|ident| !LIFECYCLE_METHODS.iter().any(|method| ident == method))
+            .filter(|ident| {
+                let is_generated = GENERATED_METHODS.iter().any(|method| ident == method);
+                if is_generated {
+                    errors.push(
+                        darling::Error::custom(format!(
+                            "`{ident}` is generated by `#[event_handler]`"
+                        ))
+                        .with_span(ident),
+                    );
+                }
+
+                !is_generated
+            })
+            .cloned()
+            .collect();
+
+        errors.finish_with(Self { item, events })
+    }
+}
+
+impl quote::ToTokens for EventHandler {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        // Event methods are named after their event type flag, in snake case.
+        let event_flags = self
+            .events
+            .iter()
+            .map(|event| format_ident!("{}", event.to_string().to_uppercase(), span = event.span()))
+            .collect::<Vec<_>>();
+
+        let mut item = self.item.clone();
+        item.items.push(syn::parse_quote! {
+            fn used_event_flags(&self) -> twilight_gateway::EventTypeFlags {
+                twilight_gateway::EventTypeFlags::empty()
+                    #(| twilight_gateway::EventTypeFlags::#event_flags)*
+            }
+        });
+        item.items.push(syn::parse_quote! {
+            fn required_intents(&self) -> twilight_gateway::Intents {
+                twilight_gateway::Intents::empty()
+                    #(| bouncer_framework::event_handler::event_intents::#event_flags)*
+            }
+        });
+
+        item.to_tokens(tokens);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::ToTokens as _;
+
+    use super::*;
+
+    #[test]
+    fn test_event_flags_and_intents() {
+        let event_handler = EventHandler::from_item_impl(syn::parse_quote! {
+            impl EventHandler for Events {
+                async fn ready(&self, context: Context, ready: Box<Ready>) {}
+                async fn message_create(&self, context: Context, message: Box<MessageCreate>) {}
+                async fn shutdown(&self, context: Context) {}
+            }
+        })
+        .unwrap();
+        let expanded = event_handler.to_token_stream().to_string();
+
+        assert!(expanded.contains(
+            "twilight_gateway :: EventTypeFlags :: empty () | twilight_gateway :: EventTypeFlags :: READY | twilight_gateway :: EventTypeFlags :: MESSAGE_CREATE"
+        ));
+        assert!(expanded.contains(
+            "twilight_gateway :: Intents :: empty () | bouncer_framework :: event_handler :: event_intents :: READY | bouncer_framework :: event_handler :: event_intents :: MESSAGE_CREATE"
+        ));
+        assert!(!expanded.contains("SHUTDOWN"));
+    }
+
+    #[test]
+    fn test_reject_generated_methods() {
+        let error = EventHandler::from_item_impl(syn::parse_quote! {
+            impl EventHandler for Events {
+                fn used_event_flags(&self) -> EventTypeFlags {
+                    EventTypeFlags::all()
+                }
+            }
+        })
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "`used_event_flags` is generated by `#[event_handler]`"
+        );
+    }
+}
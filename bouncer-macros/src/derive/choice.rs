@@ -0,0 +1,337 @@
+use darling::{FromDeriveInput, FromVariant};
+use quote::quote;
+use twilight_validate::command;
+
+use crate::derive::{
+    command::validate_option_value,
+    localizations::{Localizations, localizations_tokens, parse_localizations},
+};
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(supports(enum_unit))]
+pub struct CommandChoice {
+    ident: syn::Ident,
+    data: darling::ast::Data<CommandChoiceVariant, darling::util::Ignored>,
+}
+
+#[derive(Debug, FromVariant)]
+#[darling(attributes(choice))]
+pub struct CommandChoiceVariant {
+    ident: syn::Ident,
+
+    name: Option<String>,
+    value: Option<syn::Lit>,
+    name_localizations: Option<Localizations>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CommandChoiceValue {
+    String(String),
+    Integer(i64),
+}
+
+impl CommandChoiceVariant {
+    fn choice_name(&self) -> darling::Result<String> {
+        let name = self.name.clone().unwrap_or_else(|| self.ident.to_string());
+        validate_choice_name(&name)
+            .map(|()| name)
+            .map_err(|error| error.with_span(&self.ident))
+    }
+
+    fn choice_name_localizations(&self) -> darling::Result<Vec<(&'static str, String)>> {
+        parse_localizations(self.name_localizations.as_ref(), |name| {
+            validate_choice_name(name).map(|()| name.to_string())
+        })
+    }
+
+    fn choice_value(&self) -> darling::Result<CommandChoiceValue> {
+        match &self.value {
+            None => Ok(CommandChoiceValue::String(
+                self.ident.to_string().to_lowercase(),
+            )),
+            Some(syn::Lit::Str(value)) => validate_choice_string_value(&value.value())
+                .map(|()| CommandChoiceValue::String(value.value()))
+                .map_err(|error| error.with_span(value)),
+            Some(syn::Lit::Int(value)) => value
+                .base10_parse()
+                .map_err(darling::Error::from)
+                .and_then(|int| {
+                    validate_option_value(int).map(|()| CommandChoiceValue::Integer(int))
+                })
+                .map_err(|error| error.with_span(value)),
+            Some(value) => {
+                Err(darling::Error::custom("Expected a string or integer value").with_span(value))
+            }
+        }
+    }
+}
+
+impl quote::ToTokens for CommandChoice {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let ident = &self.ident;
+
+        let darling::ast::Data::Enum(variants) = &self.data else {
+            unreachable!()
+        };
+
+        if variants.is_empty() {
+            tokens.extend(
+                darling::Error::custom("An option needs at least one choice")
+                    .with_span(ident)
+                    .write_errors(),
+            );
+
+            return;
+        }
+
+        if variants.len() > command::CHOICES_LIMIT {
+            tokens.extend(
+                darling::Error::custom(format!(
+                    "An option can have at most {} choices",
+                    command::CHOICES_LIMIT
+                ))
+                .with_span(ident)
+                .write_errors(),
+            );
+
+            return;
+        }
+
+        let mut errors = darling::Error::accumulator();
+        let choices = variants
+            .iter()
+            .filter_map(|variant| {
+                let name = errors.handle(variant.choice_name())?;
+                let value = errors.handle(variant.choice_value())?;
+                let name_localizations = errors.handle(variant.choice_name_localizations())?;
+
+                Some((&variant.ident, name, value, name_localizations))
+            })
+            .collect::<Vec<_>>();
+
+        if let Err(error) = errors.finish() {
+            tokens.extend(error.write_errors());
+            return;
+        }
+
+        let is_integer = choices
+            .first()
+            .is_some_and(|(_, _, value, _)| matches!(value, CommandChoiceValue::Integer(_)));
+        if choices
+            .iter()
+            .any(|(_, _, value, _)| matches!(value, CommandChoiceValue::Integer(_)) != is_integer)
+        {
+            tokens.extend(
+                darling::Error::custom("Choice values must all be strings or all be integers")
+                    .with_span(ident)
+                    .write_errors(),
+            );
+
+            return;
+        }
+
+        let mut errors = darling::Error::accumulator();
+        for (index, (variant_ident, _, value, _)) in choices.iter().enumerate() {
+            if choices[..index]
+                .iter()
+                .any(|(_, _, previous, _)| previous == value)
+            {
+                errors.push(
+                    darling::Error::custom("Choice values must be unique").with_span(variant_ident),
+                );
+            }
+        }
+
+        if let Err(error) = errors.finish() {
+            tokens.extend(error.write_errors());
+            return;
+        }
+
+        let variant_idents = choices.iter().map(|(ident, _, _, _)| ident);
+        let choice_names = choices
+            .iter()
+            .map(|(_, name, _, _)| name)
+            .collect::<Vec<_>>();
+        let choice_name_localizations = choices
+            .iter()
+            .map(|(_, _, _, name_localizations)| localizations_tokens(name_localizations));
+        let (choice_values, value_patterns): (Vec<_>, Vec<_>) = choices
+            .iter()
+            .map(|(_, _, value, _)| match value {
+                CommandChoiceValue::String(value) => (quote!(#value.to_string()), quote!(#value)),
+                CommandChoiceValue::Integer(value) => (quote!(#value), quote!(#value)),
+            })
+            .unzip();
+
+        let (kind, matched_value) = if is_integer {
+            (quote!(Integer), quote!(*value))
+        } else {
+            (quote!(String), quote!(value.as_str()))
+        };
+
+        tokens.extend(quote! {
+            impl bouncer_framework::command::CommandChoice for #ident {
+                const OPTION_TYPE: twilight_model::application::command::CommandOptionType =
+                    twilight_model::application::command::CommandOptionType::#kind;
+
+                fn choices() -> Vec<twilight_model::application::command::CommandOptionChoice> {
+                    vec![
+                        #(
+                            twilight_model::application::command::CommandOptionChoice {
+                                name: #choice_names.to_string(),
+                                name_localizations: bouncer_framework::command::localizations(
+                                    &#choice_name_localizations,
+                                ),
+                                value: twilight_model::application::command::CommandOptionChoiceValue::#kind(
+                                    #choice_values,
+                                ),
+                            }
+                        ),*
+                    ]
+                }
+
+                fn from_option_value(
+                    name: &str,
+                    value: &twilight_model::application::interaction::application_command::CommandOptionValue,
+                ) -> Result<Self, bouncer_framework::command::CommandOptionsError> {
+                    let twilight_model::application::interaction::application_command::CommandOptionValue::#kind(value) = value else {
+                        return Err(bouncer_framework::command::CommandOptionsError::UnexpectedOptionType(
+                            name.to_string(),
+                            value.kind(),
+                        ));
+                    };
+
+                    match #matched_value {
+                        #(#value_patterns => Ok(Self::#variant_idents),)*
+                        _ => Err(bouncer_framework::command::CommandOptionsError::UnknownChoice(
+                            name.to_string(),
+                            value.to_string(),
+                        )),
+                    }
+                }
+            }
+        });
+    }
+}
+
+fn validate_choice_name(name: &str) -> darling::Result<()> {
+    let length = name.chars().count();
+    if !(command::OPTION_CHOICE_NAME_LENGTH_MIN..=command::OPTION_CHOICE_NAME_LENGTH_MAX)
+        .contains(&length)
+    {
+        return Err(darling::Error::custom(format!(
+            "Choice name must be between {} and {} characters long",
+            command::OPTION_CHOICE_NAME_LENGTH_MIN,
+            command::OPTION_CHOICE_NAME_LENGTH_MAX
+        )));
+    }
+
+    Ok(())
+}
+
+fn validate_choice_string_value(value: &str) -> darling::Result<()> {
+    let length = value.chars().count();
+    if !(command::OPTION_CHOICE_STRING_VALUE_LENGTH_MIN
+        ..=command::OPTION_CHOICE_STRING_VALUE_LENGTH_MAX)
+        .contains(&length)
+    {
+        return Err(darling::Error::custom(format!(
+            "Choice value must be between {} and {} characters long",
+            command::OPTION_CHOICE_STRING_VALUE_LENGTH_MIN,
+            command::OPTION_CHOICE_STRING_VALUE_LENGTH_MAX
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::{ToTokens as _, format_ident};
+
+    use super::*;
+
+    fn expand(input: &syn::DeriveInput) -> String {
+        match CommandChoice::from_derive_input(input) {
+            Ok(command_choice) => command_choice.to_token_stream(),
+            Err(error) => error.write_errors(),
+        }
+        .to_string()
+    }
+
+    #[test]
+    fn test_string_choices() {
+        let expanded = expand(&syn::parse_quote! {
+            enum Unit {
+                Seconds,
+                #[choice(name = "Minutes", value = "min")]
+                Minutes,
+            }
+        });
+
+        assert!(!expanded.contains("compile_error"));
+        assert!(expanded.contains("CommandOptionType :: String"));
+        assert!(expanded.contains("name : \"Seconds\" . to_string ()"));
+        assert!(expanded.contains("\"seconds\" => Ok (Self :: Seconds)"));
+        assert!(expanded.contains("\"min\" => Ok (Self :: Minutes)"));
+    }
+
+    #[test]
+    fn test_integer_choices() {
+        let expanded = expand(&syn::parse_quote! {
+            enum Days {
+                #[choice(value = 1)]
+                One,
+                #[choice(value = 7)]
+                Seven,
+            }
+        });
+
+        assert!(!expanded.contains("compile_error"));
+        assert!(expanded.contains("CommandOptionType :: Integer"));
+        assert!(expanded.contains("7i64 => Ok (Self :: Seven)"));
+    }
+
+    #[test]
+    fn test_reject_invalid_choices() {
+        let expanded = expand(&syn::parse_quote! {
+            enum Mixed {
+                #[choice(value = 1)]
+                One,
+                #[choice(value = "two")]
+                Two,
+            }
+        });
+
+        assert!(expanded.contains("Choice values must all be strings or all be integers"));
+
+        let expanded = expand(&syn::parse_quote! {
+            enum Duplicate {
+                #[choice(value = "same")]
+                First,
+                #[choice(value = "same")]
+                Second,
+            }
+        });
+
+        assert!(expanded.contains("Choice values must be unique"));
+
+        let expanded = expand(&syn::parse_quote! {
+            enum Empty {}
+        });
+
+        assert!(expanded.contains("An option needs at least one choice"));
+    }
+
+    #[test]
+    fn test_too_many_choices() {
+        let variants = (0..=command::CHOICES_LIMIT).map(|index| format_ident!("Choice{index}"));
+        let expanded = expand(&syn::parse_quote! {
+            enum Many {
+                #(#variants),*
+            }
+        });
+
+        assert!(expanded.contains("An option can have at most 25 choices"));
+    }
+}
@@ -1,30 +1,98 @@
-use darling::{FromDeriveInput, FromField, FromMeta as _};
+use darling::{FromDeriveInput, FromField, FromMeta};
 use quote::{format_ident, quote};
 use syn::spanned::Spanned as _;
+use twilight_model::{application::command::CommandOptionValue, channel::ChannelType};
 use twilight_validate::command;
 
+use crate::derive::{
+    commands::CommandsVariant,
+    localizations::{Localizations, localizations_tokens, parse_localizations},
+};
+
 #[derive(Debug, FromDeriveInput)]
-#[darling(attributes(command, option), supports(struct_named, struct_unit))]
+#[darling(
+    attributes(command, option),
+    supports(struct_named, struct_unit, enum_newtype)
+)]
 pub struct Command {
     ident: syn::Ident,
-    data: darling::ast::Data<darling::util::Ignored, CommandOptionField>,
+    vis: syn::Visibility,
+    data: darling::ast::Data<CommandsVariant, CommandOptionField>,
 
-    #[darling(with = Command::parse_command_name)]
     name: Option<String>,
     #[darling(with = Command::parse_command_description)]
-    description: String,
+    description: Option<String>,
+    kind: Option<CommandKind>,
+    name_localizations: Option<Localizations>,
+    description_localizations: Option<Localizations>,
+
+    default_member_permissions: Option<darling::util::PathList>,
+    dm_permission: Option<bool>,
+    contexts: Option<darling::util::PathList>,
+    integration_types: Option<darling::util::PathList>,
+    nsfw: darling::util::Flag,
+    /// Whether the response is deferred as ephemeral, overriding the
+    /// registry's setting.
+    defer_ephemeral: Option<bool>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, FromMeta)]
+#[darling(rename_all = "snake_case")]
+pub enum CommandKind {
+    #[default]
+    ChatInput,
+    User,
+    Message,
+}
+
+#[derive(Debug, Clone, Copy)]
 pub enum CommandOptionType {
     Boolean,
     String,
+    Integer,
+    Number,
+    User,
+    Channel,
+    Role,
+    Mentionable,
+    Attachment,
+}
+
+/// Resolved data an option's value is looked up in, for fields typed as the
+/// resolved model rather than its ID.
+#[derive(Debug, Clone, Copy)]
+pub enum ResolvedOptionData {
+    Users,
+    Members,
+    Channels,
+    Roles,
+    Attachments,
+    Messages,
+}
+
+#[derive(Debug)]
+pub enum CommandOptionValueType {
+    /// A value Discord sends as-is, optionally looked up in the resolved data.
+    Value {
+        kind: CommandOptionType,
+        resolved: Option<ResolvedOptionData>,
+    },
+    /// A type implementing `CommandChoice`.
+    Choice(Box<syn::Type>),
 }
 
 #[derive(Debug)]
 pub enum CommandOption {
-    Optional(CommandOptionType),
-    Required(CommandOptionType),
+    Optional(CommandOptionValueType),
+    Required(CommandOptionValueType),
+}
+
+/// How a context menu command's field is filled in from the command's target.
+#[derive(Debug)]
+pub enum CommandTarget {
+    Id,
+    Resolved(ResolvedOptionData),
+    OptionalResolved(ResolvedOptionData),
 }
 
 #[derive(Debug, FromField)]
@@ -36,18 +104,183 @@ pub struct CommandOptionField {
     #[darling(with = CommandOptionField::parse_command_option_name)]
     name: Option<String>,
     #[darling(with = CommandOptionField::parse_command_description)]
-    description: String,
+    description: Option<String>,
+    name_localizations: Option<Localizations>,
+    description_localizations: Option<Localizations>,
+
+    min_value: Option<syn::Expr>,
+    max_value: Option<syn::Expr>,
+    min_length: Option<u16>,
+    max_length: Option<u16>,
+    channel_types: Option<darling::util::PathList>,
+    autocomplete: darling::util::Flag,
+    /// Whether the field's type implements `CommandChoice`.
+    choice: darling::util::Flag,
+}
+
+/// Builder calls and parse-time checks generated from an option's
+/// constraints.
+#[derive(Debug, Default)]
+pub struct CommandOptionConstraints {
+    builder: proc_macro2::TokenStream,
+    check: Option<proc_macro2::TokenStream>,
 }
 
 impl Command {
-    fn parse_command_name(meta: &syn::Meta) -> darling::Result<Option<String>> {
-        let command_name = String::from_meta(meta)?.to_lowercase();
-        validate_command_chat_input_name(&command_name).map(|()| Some(command_name))
+    fn parse_command_description(meta: &syn::Meta) -> darling::Result<Option<String>> {
+        let command_description = String::from_meta(meta)?;
+        validate_command_description(&command_description).map(|()| Some(command_description))
     }
 
-    fn parse_command_description(meta: &syn::Meta) -> darling::Result<String> {
-        let command_description = String::from_meta(meta)?;
-        validate_command_description(&command_description).map(|()| command_description)
+    fn kind(&self) -> CommandKind {
+        self.kind.unwrap_or_default()
+    }
+
+    /// Returns the command's name, lowercased for slash commands, which only
+    /// allow lowercase names.
+    fn command_name(&self) -> darling::Result<String> {
+        let name = self.name.clone().unwrap_or_else(|| self.ident.to_string());
+
+        match self.kind() {
+            CommandKind::ChatInput => {
+                let name = name.to_lowercase();
+                validate_command_chat_input_name(&name).map(|()| name)
+            }
+            CommandKind::User | CommandKind::Message => validate_command_name(&name).map(|()| name),
+        }
+        .map_err(|error| error.with_span(&self.ident))
+    }
+
+    /// Generates the builder calls for who can see the command and where it
+    /// can be used.
+    ///
+    /// These only apply to top-level commands, and are ignored when the
+    /// command is used as a subcommand.
+    fn generate_command_builder(&self) -> darling::Result<proc_macro2::TokenStream> {
+        let mut errors = darling::Error::accumulator();
+        let mut builder = proc_macro2::TokenStream::new();
+
+        if let Some(permissions) = &self.default_member_permissions {
+            for permission in permissions.iter() {
+                errors.handle(validate_permission(permission));
+            }
+
+            // No permissions restrict the command to administrators.
+            let permissions = if permissions.is_empty() {
+                quote!(twilight_model::guild::Permissions::empty())
+            } else {
+                let permissions = permissions.iter();
+                quote!(#(twilight_model::guild::Permissions::#permissions)|*)
+            };
+
+            builder.extend(quote!(.default_member_permissions(#permissions)));
+        }
+
+        // `dm_permission` is deprecated in favor of `contexts`, so it's
+        // expressed with the contexts it's equivalent to.
+        let contexts = match (self.dm_permission, &self.contexts) {
+            (Some(_), Some(_)) => {
+                errors.push(
+                    darling::Error::custom("`dm_permission` can't be combined with `contexts`")
+                        .with_span(&self.ident),
+                );
+                None
+            }
+            (Some(true), None) => Some(vec![quote!(Guild), quote!(BotDm)]),
+            (Some(false), None) => Some(vec![quote!(Guild)]),
+            (None, Some(contexts)) => Some(
+                contexts
+                    .iter()
+                    .map(|context| {
+                        errors.handle(validate_context(context));
+                        quote!(#context)
+                    })
+                    .collect(),
+            ),
+            (None, None) => None,
+        };
+
+        if let Some(contexts) = contexts {
+            builder.extend(quote! {
+                .contexts([
+                    #(twilight_model::application::interaction::InteractionContextType::#contexts),*
+                ])
+            });
+        }
+
+        if let Some(integration_types) = &self.integration_types {
+            for integration_type in integration_types.iter() {
+                errors.handle(validate_integration_type(integration_type));
+            }
+
+            let integration_types = integration_types.iter();
+            builder.extend(quote! {
+                .integration_types([
+                    #(twilight_model::oauth::ApplicationIntegrationType::#integration_types),*
+                ])
+            });
+        }
+
+        if self.nsfw.is_present() {
+            builder.extend(quote!(.nsfw(true)));
+        }
+
+        errors.finish_with(builder)
+    }
+
+    /// Generates the command's choice of whether its response is deferred as
+    /// ephemeral, if it makes one.
+    fn generate_defer_ephemeral(&self) -> Option<proc_macro2::TokenStream> {
+        self.defer_ephemeral
+            .map(|ephemeral| quote!(const DEFER_EPHEMERAL: Option<bool> = Some(#ephemeral);))
+    }
+
+    /// Generates the builder calls for the command's localized names and
+    /// descriptions.
+    fn generate_command_localizations(&self) -> darling::Result<proc_macro2::TokenStream> {
+        let kind = self.kind();
+        if kind != CommandKind::ChatInput && self.description_localizations.is_some() {
+            return Err(
+                darling::Error::custom("Context menu commands can't have a description")
+                    .with_span(&self.ident),
+            );
+        }
+
+        let name_localizations =
+            parse_localizations(self.name_localizations.as_ref(), |name| match kind {
+                CommandKind::ChatInput => {
+                    let name = name.to_lowercase();
+                    validate_command_chat_input_name(&name).map(|()| name)
+                }
+                CommandKind::User | CommandKind::Message => {
+                    validate_command_name(name).map(|()| name.to_string())
+                }
+            })?;
+        let description_localizations =
+            parse_localizations(self.description_localizations.as_ref(), |description| {
+                validate_command_description(description).map(|()| description.to_string())
+            })?;
+
+        Ok(generate_localization_builder(
+            &name_localizations,
+            &description_localizations,
+        ))
+    }
+
+    /// Returns the command's description, which slash commands require and
+    /// context menu commands can't have.
+    fn command_description(&self) -> darling::Result<&str> {
+        match (self.kind(), &self.description) {
+            (CommandKind::ChatInput, Some(description)) => Ok(description),
+            (CommandKind::ChatInput, None) => {
+                Err(darling::Error::custom("Missing `description`").with_span(&self.ident))
+            }
+            (CommandKind::User | CommandKind::Message, None) => Ok(""),
+            (CommandKind::User | CommandKind::Message, Some(_)) => Err(darling::Error::custom(
+                "Context menu commands can't have a description",
+            )
+            .with_span(&self.ident)),
+        }
     }
 }
 
@@ -57,18 +290,318 @@ impl CommandOptionField {
         validate_option_name(&option_name).map(|()| Some(option_name))
     }
 
-    fn parse_command_description(meta: &syn::Meta) -> darling::Result<String> {
+    fn parse_command_description(meta: &syn::Meta) -> darling::Result<Option<String>> {
         let option_description = String::from_meta(meta)?;
-        validate_command_description(&option_description).map(|()| option_description)
+        validate_command_description(&option_description).map(|()| Some(option_description))
     }
 
-    fn generate_option_builders(&self) -> proc_macro2::TokenStream {
+    fn option_description(&self) -> darling::Result<&str> {
+        self.description.as_deref().ok_or_else(|| {
+            darling::Error::custom("Missing `description`").with_span(&self.ident.as_ref())
+        })
+    }
+
+    fn has_option_attributes(&self) -> bool {
+        self.name.is_some()
+            || self.description.is_some()
+            || self.name_localizations.is_some()
+            || self.description_localizations.is_some()
+            || self.min_value.is_some()
+            || self.max_value.is_some()
+            || self.min_length.is_some()
+            || self.max_length.is_some()
+            || self.channel_types.is_some()
+            || self.autocomplete.is_present()
+            || self.choice.is_present()
+    }
+
+    /// Returns the option the field is parsed from, whose type implements
+    /// `CommandChoice` if the field is marked with `#[option(choice)]`.
+    fn command_option(&self) -> darling::Result<CommandOption> {
+        if !self.choice.is_present() {
+            return CommandOption::try_from(&self.ty);
+        }
+
+        let choice = |ty: &syn::Type| CommandOptionValueType::Choice(Box::new(ty.clone()));
+        let path_segment = extract_path_segment(&self.ty)?;
+        if path_segment.ident == "Option" {
+            if let Some(inner_type) = extract_generic_argument(path_segment) {
+                return Ok(CommandOption::Optional(choice(inner_type)));
+            }
+        }
+
+        Ok(CommandOption::Required(choice(&self.ty)))
+    }
+
+    /// Returns how the field is filled in from the target of a context menu
+    /// command of the given kind.
+    fn command_target(&self, kind: CommandKind) -> darling::Result<CommandTarget> {
+        if self.has_option_attributes() {
+            return Err(
+                darling::Error::custom("Context menu commands can't have options")
+                    .with_span(&self.ident.as_ref()),
+            );
+        }
+
+        let path_segment = extract_path_segment(&self.ty)?;
+        let type_name = path_segment.ident.to_string();
+        let marker_name = extract_generic_argument(path_segment)
+            .and_then(|ty| extract_path_segment(ty).ok())
+            .map(|path_segment| path_segment.ident.to_string());
+
+        let target = match (kind, type_name.as_str(), marker_name.as_deref()) {
+            (CommandKind::User, "Id", Some("UserMarker"))
+            | (CommandKind::Message, "Id", Some("MessageMarker")) => Some(CommandTarget::Id),
+            (CommandKind::User, "User", _) => {
+                Some(CommandTarget::Resolved(ResolvedOptionData::Users))
+            }
+            (CommandKind::User, "InteractionMember", _) => {
+                Some(CommandTarget::Resolved(ResolvedOptionData::Members))
+            }
+            (CommandKind::User, "Option", Some("InteractionMember")) => {
+                Some(CommandTarget::OptionalResolved(ResolvedOptionData::Members))
+            }
+            (CommandKind::Message, "Message", _) => {
+                Some(CommandTarget::Resolved(ResolvedOptionData::Messages))
+            }
+            _ => None,
+        };
+
+        target.ok_or_else(|| {
+            let expected = match kind {
+                CommandKind::User => {
+                    "`Id<UserMarker>`, `User`, `InteractionMember` or `Option<InteractionMember>`"
+                }
+                _ => "`Id<MessageMarker>` or `Message`",
+            };
+
+            darling::Error::custom(format!(
+                "Unsupported target type `{type_name}`, expected {expected}"
+            ))
+            .with_span(&path_segment.ident.span())
+        })
+    }
+
+    fn generate_target_parsers(&self, target: &CommandTarget) -> proc_macro2::TokenStream {
         let ident = self.ident.as_ref().unwrap();
-        let field_name = ident.to_string().to_lowercase();
-        let option_name = self.name.as_deref().unwrap_or(&field_name);
-        let option_description = &self.description;
 
-        let result = CommandOption::try_from(&self.ty);
+        match target {
+            CommandTarget::Id => quote! {
+                let #ident = bouncer_framework::command::parse_target_id(data)?;
+            },
+            CommandTarget::Resolved(resolved_data) => quote! {
+                let #ident = bouncer_framework::command::resolve_target(data, |resolved, id| {
+                    resolved.#resolved_data.get(&id).cloned()
+                })?;
+            },
+            CommandTarget::OptionalResolved(resolved_data) => quote! {
+                let #ident = bouncer_framework::command::resolve_target(data, |resolved, id| {
+                    Some(resolved.#resolved_data.get(&id).cloned())
+                })?;
+            },
+        }
+    }
+
+    fn option_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| self.ident.as_ref().unwrap().to_string().to_lowercase())
+    }
+
+    fn generate_option_constraints(
+        &self,
+        option_type: &CommandOptionValueType,
+    ) -> darling::Result<CommandOptionConstraints> {
+        let has_value_range = self.min_value.is_some() || self.max_value.is_some();
+        let has_length_range = self.min_length.is_some() || self.max_length.is_some();
+
+        let kind = match option_type {
+            CommandOptionValueType::Value { kind, .. } => *kind,
+            CommandOptionValueType::Choice(_) => {
+                if has_value_range
+                    || has_length_range
+                    || self.channel_types.is_some()
+                    || self.autocomplete.is_present()
+                {
+                    return Err(darling::Error::custom(
+                        "Options with choices can't have constraints or autocomplete",
+                    )
+                    .with_span(&self.ty));
+                }
+
+                return Ok(CommandOptionConstraints::default());
+            }
+        };
+
+        let option_name = self.option_name();
+        let mut constraints = CommandOptionConstraints::default();
+
+        if self.autocomplete.is_present() {
+            if !matches!(
+                kind,
+                CommandOptionType::String | CommandOptionType::Integer | CommandOptionType::Number
+            ) {
+                return Err(darling::Error::custom(
+                    "`autocomplete` is only supported on string, integer and number options",
+                )
+                .with_span(&self.ty));
+            }
+
+            constraints.builder.extend(quote!(.autocomplete(true)));
+        }
+
+        let (min_value, max_value) = match kind {
+            _ if !has_value_range => (None, None),
+            CommandOptionType::Integer => (
+                parse_integer_constraint(self.min_value.as_ref())?.map(CommandOptionValue::Integer),
+                parse_integer_constraint(self.max_value.as_ref())?.map(CommandOptionValue::Integer),
+            ),
+            CommandOptionType::Number => (
+                parse_number_constraint(self.min_value.as_ref())?.map(CommandOptionValue::Number),
+                parse_number_constraint(self.max_value.as_ref())?.map(CommandOptionValue::Number),
+            ),
+            _ => {
+                return Err(darling::Error::custom(
+                    "`min_value` and `max_value` are only supported on integer and number options",
+                )
+                .with_span(&self.ty));
+            }
+        };
+
+        if has_value_range {
+            validate_option_value_range(min_value, max_value)
+                .map_err(|error| error.with_span(&self.ty))?;
+
+            let min_value = min_value.map(option_value_tokens);
+            let max_value = max_value.map(option_value_tokens);
+            let min_value_option = optional_tokens(min_value.as_ref());
+            let max_value_option = optional_tokens(max_value.as_ref());
+            let min_value = min_value.map(|value| quote!(.min_value(#value)));
+            let max_value = max_value.map(|value| quote!(.max_value(#value)));
+
+            constraints.builder.extend(quote!(#min_value #max_value));
+            constraints.check = Some(quote! {
+                bouncer_framework::command::validate_option_value(
+                    #option_name,
+                    value,
+                    #min_value_option,
+                    #max_value_option,
+                )?;
+            });
+        }
+
+        if has_length_range {
+            if !matches!(kind, CommandOptionType::String) {
+                return Err(darling::Error::custom(
+                    "`min_length` and `max_length` are only supported on string options",
+                )
+                .with_span(&self.ty));
+            }
+
+            validate_option_length_range(self.min_length, self.max_length)
+                .map_err(|error| error.with_span(&self.ty))?;
+
+            let min_length_option = optional_tokens(self.min_length.as_ref());
+            let max_length_option = optional_tokens(self.max_length.as_ref());
+            let min_length = self.min_length.map(|length| quote!(.min_length(#length)));
+            let max_length = self.max_length.map(|length| quote!(.max_length(#length)));
+
+            constraints.builder.extend(quote!(#min_length #max_length));
+            constraints.check = Some(quote! {
+                bouncer_framework::command::validate_option_length(
+                    #option_name,
+                    value,
+                    #min_length_option,
+                    #max_length_option,
+                )?;
+            });
+        }
+
+        let channel_types = self
+            .channel_types
+            .as_ref()
+            .map(|channel_types| {
+                channel_types
+                    .iter()
+                    .map(parse_channel_type)
+                    .collect::<darling::Result<Vec<_>>>()
+            })
+            .transpose()?;
+
+        if let Some(channel_types) = &channel_types {
+            if !matches!(kind, CommandOptionType::Channel) {
+                return Err(darling::Error::custom(
+                    "`channel_types` is only supported on channel options",
+                )
+                .with_span(&self.ty));
+            }
+
+            let channel_types = channel_types.iter().map(|channel_type| {
+                let variant = format_ident!("{}", channel_type.name());
+                quote!(twilight_model::channel::ChannelType::#variant)
+            });
+            let channel_types = channel_types.collect::<Vec<_>>();
+
+            constraints.builder.extend(quote! {
+                .channel_types([#(#channel_types),*])
+            });
+            constraints.check = Some(quote! {
+                bouncer_framework::command::validate_option_channel_type(
+                    #option_name,
+                    *value,
+                    resolved,
+                    &[#(#channel_types),*],
+                )?;
+            });
+        }
+
+        // Runs the option through twilight's validation too, to catch what the
+        // checks above don't.
+        command::option(&twilight_model::application::command::CommandOption {
+            autocomplete: Some(self.autocomplete.is_present()),
+            channel_types,
+            choices: None,
+            description: self.description.clone().unwrap_or_default(),
+            description_localizations: None,
+            kind: kind.into(),
+            max_length: self.max_length,
+            max_value,
+            min_length: self.min_length,
+            min_value,
+            name: option_name,
+            name_localizations: None,
+            options: None,
+            required: None,
+        })
+        .map_err(|error| darling::Error::custom(error).with_span(&self.ty))?;
+
+        Ok(constraints)
+    }
+
+    /// Validates the option's localized names and descriptions.
+    fn option_localizations(&self) -> darling::Result<OptionLocalizations> {
+        let name_localizations = parse_localizations(self.name_localizations.as_ref(), |name| {
+            let name = name.to_lowercase();
+            validate_option_name(&name).map(|()| name)
+        })?;
+        let description_localizations =
+            parse_localizations(self.description_localizations.as_ref(), |description| {
+                validate_command_description(description).map(|()| description.to_string())
+            })?;
+
+        Ok((name_localizations, description_localizations))
+    }
+
+    fn generate_option_builders(&self) -> proc_macro2::TokenStream {
+        let option_name = self.option_name();
+        let option_description = self.description.as_deref().unwrap_or_default();
+
+        let (name_localizations, description_localizations) = match self.option_localizations() {
+            Ok(localizations) => localizations,
+            Err(error) => return error.write_errors(),
+        };
+
+        let result = self.command_option();
         match result {
             Ok(option) => {
                 let (option_type, required) = match option {
@@ -76,13 +609,42 @@ impl CommandOptionField {
                     CommandOption::Required(typ) => (typ, true),
                 };
 
-                let builder_type = format_ident!("{}Builder", option_type);
-                quote! {
-                    .option(
-                        twilight_util::builder::command::#builder_type::new(#option_name, #option_description)
-                            .required(#required)
-                            .build()
-                    )
+                let constraints = match self.generate_option_constraints(&option_type) {
+                    Ok(constraints) => constraints.builder,
+                    Err(error) => return error.write_errors(),
+                };
+
+                match option_type {
+                    CommandOptionValueType::Value { kind, .. } => {
+                        let builder_type = format_ident!("{}Builder", kind);
+                        let localizations = generate_localization_builder(
+                            &name_localizations,
+                            &description_localizations,
+                        );
+                        quote! {
+                            .option(
+                                twilight_util::builder::command::#builder_type::new(#option_name, #option_description)
+                                    #localizations
+                                    .required(#required)
+                                    #constraints
+                                    .build()
+                            )
+                        }
+                    }
+                    CommandOptionValueType::Choice(choice_type) => {
+                        let name_localizations = localizations_tokens(&name_localizations);
+                        let description_localizations =
+                            localizations_tokens(&description_localizations);
+                        quote! {
+                            .option(bouncer_framework::command::choice_option::<#choice_type>(
+                                #option_name,
+                                #option_description,
+                                #required,
+                                &#name_localizations,
+                                &#description_localizations,
+                            ))
+                        }
+                    }
                 }
             }
             Err(error) => error.write_errors(),
@@ -91,11 +653,9 @@ impl CommandOptionField {
 
     fn generate_option_parsers(&self) -> proc_macro2::TokenStream {
         let ident = self.ident.as_ref().unwrap();
-        let field_name = ident.to_string().to_lowercase();
-        let option_name = self.name.as_deref().unwrap_or(&field_name);
-        let option_name_ident = format_ident!("{option_name}");
+        let option_name = self.option_name();
 
-        let result = CommandOption::try_from(&self.ty);
+        let result = self.command_option();
         let (option_type, required) = match result {
             Ok(option) => match option {
                 CommandOption::Optional(typ) => (typ, false),
@@ -104,28 +664,386 @@ impl CommandOptionField {
             Err(error) => return error.write_errors(),
         };
 
-        if required {
-            return quote! {
-                let #option_name_ident = bouncer_framework::command::parse_required_option(
-                    options,
-                    #option_name,
-                    |value| match value {
-                        #option_type(value) => Some(value.to_owned()),
-                        _ => None,
+        // Constraint errors are already reported by the option builders.
+        let check = self
+            .generate_option_constraints(&option_type)
+            .ok()
+            .and_then(|constraints| constraints.check);
+
+        let (option_kind, resolved) = match option_type {
+            CommandOptionValueType::Value { kind, resolved } => (kind, resolved),
+            CommandOptionValueType::Choice(choice_type) => {
+                let parser = if required {
+                    quote!(bouncer_framework::command::parse_required_choice)
+                } else {
+                    quote!(bouncer_framework::command::parse_optional_choice)
+                };
+
+                return quote! {
+                    let #ident = #parser::<#choice_type>(options, #option_name)?;
+                };
+            }
+        };
+
+        let parser = if required {
+            quote!(bouncer_framework::command::parse_required_option)
+        } else {
+            quote!(bouncer_framework::command::parse_optional_option)
+        };
+
+        let mut tokens = quote! {
+            let #ident = #parser(
+                options,
+                #option_name,
+                |value| match value {
+                    #option_kind(value) => Some(value.to_owned()),
+                    _ => None,
+                }
+            )?;
+        };
+
+        if let Some(check) = check {
+            tokens.extend(if required {
+                quote! {
+                    {
+                        let value = &#ident;
+                        #check
                     }
-                )?;
+                }
+            } else {
+                quote! {
+                    if let Some(value) = &#ident {
+                        #check
+                    }
+                }
+            });
+        }
+
+        if let Some(resolved_data) = resolved {
+            let resolve = quote! {
+                bouncer_framework::command::resolve_option(
+                    resolved,
+                    #option_name,
+                    |resolved| resolved.#resolved_data.get(&id).cloned(),
+                )
             };
+
+            tokens.extend(if required {
+                quote! {
+                    let #ident = {
+                        let id = #ident;
+                        #resolve?
+                    };
+                }
+            } else {
+                quote! {
+                    let #ident = #ident.map(|id| #resolve).transpose()?;
+                }
+            });
         }
 
+        tokens
+    }
+}
+
+impl Command {
+    /// Generates a command whose variants are its subcommands or subcommand
+    /// groups.
+    fn generate_subcommands(
+        &self,
+        variants: &[CommandsVariant],
+        command_name: &str,
+        command_description: &str,
+        command_builder: &proc_macro2::TokenStream,
+        command_localizations: &proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+        let defer_ephemeral = self.generate_defer_ephemeral();
+
+        let variant_idents = variants
+            .iter()
+            .map(CommandsVariant::ident)
+            .collect::<Vec<_>>();
+        let command_types = variants
+            .iter()
+            .map(CommandsVariant::command_type)
+            .collect::<Vec<_>>();
+
         quote! {
-            let #option_name_ident = bouncer_framework::command::parse_optional_option(
+            #[async_trait::async_trait]
+            impl bouncer_framework::command::CommandData for #ident {
+                const COMMAND_NAME: &'static str = #command_name;
+                const COMMAND_DESCRIPTION: &'static str = #command_description;
+                #defer_ephemeral
+                const HAS_AUTOCOMPLETE: bool =
+                    false #(|| <#command_types as bouncer_framework::command::CommandData>::HAS_AUTOCOMPLETE)*;
+
+                fn command() -> twilight_model::application::command::Command {
+                    Self::command_builder()
+                        #command_builder
+                        #command_localizations
+                        #(.option(<#command_types as bouncer_framework::command::NestedCommand>::command_option()))*
+                        .build()
+                }
+
+                fn defer_ephemeral(
+                    options: &[twilight_model::application::interaction::application_command::CommandDataOption],
+                ) -> Option<bool> {
+                    let Some(option) = options.first() else {
+                        return Self::DEFER_EPHEMERAL;
+                    };
+                    let (
+                        twilight_model::application::interaction::application_command::CommandOptionValue::SubCommand(options)
+                        | twilight_model::application::interaction::application_command::CommandOptionValue::SubCommandGroup(options)
+                    ) = &option.value else {
+                        return Self::DEFER_EPHEMERAL;
+                    };
+
+                    #(
+                        if option.name == <#command_types as bouncer_framework::command::CommandData>::COMMAND_NAME {
+                            return <#command_types as bouncer_framework::command::CommandData>::defer_ephemeral(options)
+                                .or(Self::DEFER_EPHEMERAL);
+                        }
+                    )*
+
+                    Self::DEFER_EPHEMERAL
+                }
+
+                async fn dispatch_autocomplete(
+                    context: &bouncer_framework::Context,
+                    interaction: &twilight_model::application::interaction::Interaction,
+                    options: &[twilight_model::application::interaction::application_command::CommandDataOption],
+                    resolved: Option<&twilight_model::application::interaction::InteractionDataResolved>,
+                ) -> Result<
+                    Vec<twilight_model::application::command::CommandOptionChoice>,
+                    bouncer_framework::command::CommandError,
+                > {
+                    let option = options
+                        .first()
+                        .ok_or(bouncer_framework::command::CommandOptionsError::MissingSubCommand)?;
+                    let (
+                        twilight_model::application::interaction::application_command::CommandOptionValue::SubCommand(options)
+                        | twilight_model::application::interaction::application_command::CommandOptionValue::SubCommandGroup(options)
+                    ) = &option.value else {
+                        return Err(bouncer_framework::command::CommandOptionsError::UnexpectedOptionType(
+                            option.name.clone(),
+                            option.value.kind(),
+                        )
+                        .into());
+                    };
+
+                    #(
+                        if option.name == <#command_types as bouncer_framework::command::CommandData>::COMMAND_NAME {
+                            return <#command_types as bouncer_framework::command::CommandData>::dispatch_autocomplete(
+                                context,
+                                interaction,
+                                options,
+                                resolved,
+                            )
+                            .await;
+                        }
+                    )*
+
+                    Err(bouncer_framework::command::CommandOptionsError::UnknownSubCommand(option.name.clone()).into())
+                }
+            }
+
+            impl bouncer_framework::command::SubCommandGroup for #ident {
+                type SubCommands = (#(#command_types,)*);
+
+                fn subcommand_group() -> twilight_util::builder::command::SubCommandGroupBuilder {
+                    twilight_util::builder::command::SubCommandGroupBuilder::new(
+                        <Self as bouncer_framework::command::CommandData>::COMMAND_NAME,
+                        <Self as bouncer_framework::command::CommandData>::COMMAND_DESCRIPTION,
+                    )
+                    #command_localizations
+                }
+            }
+
+            impl bouncer_framework::command::CommandOptions for #ident {
+                fn parse_options(
+                    options: &[twilight_model::application::interaction::application_command::CommandDataOption],
+                    resolved: Option<&twilight_model::application::interaction::InteractionDataResolved>,
+                ) -> Result<Self, bouncer_framework::command::CommandOptionsError>
+                {
+                    let option = options
+                        .first()
+                        .ok_or(bouncer_framework::command::CommandOptionsError::MissingSubCommand)?;
+                    let (
+                        twilight_model::application::interaction::application_command::CommandOptionValue::SubCommand(options)
+                        | twilight_model::application::interaction::application_command::CommandOptionValue::SubCommandGroup(options)
+                    ) = &option.value else {
+                        return Err(bouncer_framework::command::CommandOptionsError::UnexpectedOptionType(
+                            option.name.clone(),
+                            option.value.kind(),
+                        ));
+                    };
+
+                    #(
+                        if option.name == <#command_types as bouncer_framework::command::CommandData>::COMMAND_NAME {
+                            return Ok(Self::#variant_idents(
+                                <#command_types as bouncer_framework::command::CommandOptions>::parse_options(options, resolved)?,
+                            ));
+                        }
+                    )*
+
+                    Err(bouncer_framework::command::CommandOptionsError::UnknownSubCommand(option.name.clone()))
+                }
+            }
+
+            #[async_trait::async_trait]
+            impl bouncer_framework::command::Command for #ident {
+                async fn execute(
+                    &self,
+                    context: &bouncer_framework::Context,
+                    interaction: &twilight_model::application::interaction::Interaction,
+                ) -> Result<(), bouncer_framework::command::CommandExecuteError> {
+                    match *self {
+                        #(
+                            Self::#variant_idents(ref command) => {
+                                bouncer_framework::command::Command::execute(command, context, interaction).await
+                            }
+                        )*
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl CommandOptionField {
+    /// Generates a lenient parser for autocomplete interactions, where any
+    /// option may be missing, focused or not yet valid.
+    fn generate_partial_option_parsers(&self) -> proc_macro2::TokenStream {
+        let ident = self.ident.as_ref().unwrap();
+        let option_name = self.option_name();
+
+        let Ok(CommandOption::Optional(option_type) | CommandOption::Required(option_type)) =
+            self.command_option()
+        else {
+            return proc_macro2::TokenStream::new();
+        };
+
+        let (option_kind, resolved) = match option_type {
+            CommandOptionValueType::Value { kind, resolved } => (kind, resolved),
+            CommandOptionValueType::Choice(choice_type) => {
+                return quote! {
+                    let #ident = bouncer_framework::command::parse_optional_choice::<#choice_type>(
+                        options,
+                        #option_name,
+                    )
+                    .ok()
+                    .flatten();
+                };
+            }
+        };
+
+        let mut tokens = quote! {
+            let #ident = bouncer_framework::command::parse_optional_option(
                 options,
                 #option_name,
                 |value| match value {
-                    #option_type(value) => Some(value.to_owned()),
+                    #option_kind(value) => Some(value.to_owned()),
                     _ => None,
                 }
-            )?;
+            )
+            .ok()
+            .flatten();
+        };
+
+        if let Some(resolved_data) = resolved {
+            tokens.extend(quote! {
+                let #ident = #ident.and_then(|id| {
+                    bouncer_framework::command::resolve_option(
+                        resolved,
+                        #option_name,
+                        |resolved| resolved.#resolved_data.get(&id).cloned(),
+                    )
+                    .ok()
+                });
+            });
+        }
+
+        tokens
+    }
+
+    /// Returns the field's type in the partial options, where every option is
+    /// optional.
+    fn partial_type(&self) -> proc_macro2::TokenStream {
+        let ty = &self.ty;
+
+        match self.command_option() {
+            Ok(CommandOption::Required(_)) => quote!(Option<#ty>),
+            _ => quote!(#ty),
+        }
+    }
+}
+
+impl Command {
+    /// Generates a user or message context menu command, whose fields are
+    /// filled in from the command's target.
+    fn generate_context_menu(
+        &self,
+        fields: &darling::ast::Fields<CommandOptionField>,
+        command_name: &str,
+        command_builder: &proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+        let kind = self.kind();
+        let defer_ephemeral = self.generate_defer_ephemeral();
+
+        let mut errors = darling::Error::accumulator();
+        let target_parsers = fields
+            .iter()
+            .filter_map(|field| {
+                let target = errors.handle(field.command_target(kind))?;
+                Some(field.generate_target_parsers(&target))
+            })
+            .collect::<Vec<_>>();
+
+        if let Err(error) = errors.finish() {
+            return error.write_errors();
+        }
+
+        let field_idents = fields.iter().map(|field| field.ident.as_ref());
+        let command = if fields.is_unit() {
+            quote!(Self)
+        } else {
+            quote!(Self { #(#field_idents),* })
+        };
+
+        quote! {
+            impl bouncer_framework::command::CommandData for #ident {
+                const COMMAND_NAME: &'static str = #command_name;
+                const COMMAND_DESCRIPTION: &'static str = "";
+                const COMMAND_TYPE: twilight_model::application::command::CommandType = #kind;
+                #defer_ephemeral
+
+                fn command() -> twilight_model::application::command::Command {
+                    Self::command_builder()
+                        #command_builder
+                        .build()
+                }
+            }
+
+            impl bouncer_framework::command::CommandOptions for #ident {
+                fn parse_options(
+                    _options: &[twilight_model::application::interaction::application_command::CommandDataOption],
+                    _resolved: Option<&twilight_model::application::interaction::InteractionDataResolved>,
+                ) -> Result<Self, bouncer_framework::command::CommandOptionsError>
+                {
+                    Err(bouncer_framework::command::CommandOptionsError::MissingTarget)
+                }
+
+                #[allow(unused_variables)]
+                fn parse_command_data(
+                    data: &twilight_model::application::interaction::application_command::CommandData,
+                ) -> Result<Self, bouncer_framework::command::CommandOptionsError> {
+                    #(#target_parsers)*
+
+                    Ok(#command)
+                }
+            }
         }
     }
 }
@@ -133,23 +1051,105 @@ impl CommandOptionField {
 impl quote::ToTokens for Command {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let ident = &self.ident;
-        let ident_lowercase = ident.to_string().to_lowercase();
+        let defer_ephemeral = self.generate_defer_ephemeral();
 
-        let command_name = self.name.as_deref().unwrap_or(&ident_lowercase);
-        let command_description = &self.description;
+        let command = self.command_name().and_then(|command_name| {
+            Ok((
+                command_name,
+                self.command_description()?,
+                self.generate_command_builder()?,
+                self.generate_command_localizations()?,
+            ))
+        });
+        let (command_name, command_description, command_builder, command_localizations) =
+            match command {
+                Ok(command) => command,
+                Err(error) => {
+                    tokens.extend(error.write_errors());
+                    return;
+                }
+            };
 
-        let darling::ast::Data::Struct(fields) = &self.data else {
-            unreachable!()
+        let fields = match &self.data {
+            darling::ast::Data::Struct(fields) => fields,
+            darling::ast::Data::Enum(_) if self.kind() != CommandKind::ChatInput => {
+                tokens.extend(
+                    darling::Error::custom("Context menu commands can't have subcommands")
+                        .with_span(ident)
+                        .write_errors(),
+                );
+                return;
+            }
+            darling::ast::Data::Enum(variants) if variants.len() > command::OPTIONS_LIMIT => {
+                tokens.extend(
+                    darling::Error::custom(format!(
+                        "A command can have at most {} subcommands",
+                        command::OPTIONS_LIMIT
+                    ))
+                    .with_span(ident)
+                    .write_errors(),
+                );
+                return;
+            }
+            darling::ast::Data::Enum(variants) => {
+                tokens.extend(self.generate_subcommands(
+                    variants,
+                    &command_name,
+                    command_description,
+                    &command_builder,
+                    &command_localizations,
+                ));
+                return;
+            }
         };
 
+        if self.kind() != CommandKind::ChatInput {
+            tokens.extend(self.generate_context_menu(
+                fields,
+                &command_name,
+                &quote!(#command_builder #command_localizations),
+            ));
+            return;
+        }
+
         if fields.is_unit() {
             tokens.extend(quote! {
                 impl bouncer_framework::command::CommandData for #ident {
                     const COMMAND_NAME: &'static str = #command_name;
                     const COMMAND_DESCRIPTION: &'static str = #command_description;
+                    #defer_ephemeral
 
                     fn command() -> twilight_model::application::command::Command {
-                        Self::command_builder().build()
+                        Self::command_builder()
+                            #command_builder
+                            #command_localizations
+                            .build()
+                    }
+                }
+
+                impl bouncer_framework::command::SubCommand for #ident {
+                    fn subcommand() -> twilight_util::builder::command::SubCommandBuilder {
+                        twilight_util::builder::command::SubCommandBuilder::new(
+                            <Self as bouncer_framework::command::CommandData>::COMMAND_NAME,
+                            <Self as bouncer_framework::command::CommandData>::COMMAND_DESCRIPTION,
+                        )
+                        #command_localizations
+                    }
+                }
+
+                impl bouncer_framework::command::NestedCommand for #ident {
+                    fn command_option() -> twilight_model::application::command::CommandOption {
+                        <Self as bouncer_framework::command::SubCommand>::subcommand().build()
+                    }
+                }
+
+                impl bouncer_framework::command::CommandOptions for #ident {
+                    fn parse_options(
+                        _options: &[twilight_model::application::interaction::application_command::CommandDataOption],
+                        _resolved: Option<&twilight_model::application::interaction::InteractionDataResolved>,
+                    ) -> Result<Self, bouncer_framework::command::CommandOptionsError>
+                    {
+                        Ok(Self)
                     }
                 }
             });
@@ -157,6 +1157,47 @@ impl quote::ToTokens for Command {
             return;
         }
 
+        let mut errors = darling::Error::accumulator();
+        if fields.len() > command::OPTIONS_LIMIT {
+            errors.push(
+                darling::Error::custom(format!(
+                    "A command can have at most {} options",
+                    command::OPTIONS_LIMIT
+                ))
+                .with_span(ident),
+            );
+        }
+
+        // Discord rejects commands listing a required option after an
+        // optional one, so it's reported here rather than on registration.
+        let mut has_optional_option = false;
+        for field in fields.iter() {
+            match field.command_option() {
+                Ok(CommandOption::Optional(_)) => has_optional_option = true,
+                Ok(CommandOption::Required(_)) if has_optional_option => errors.push(
+                    darling::Error::custom("Required options must come before optional ones")
+                        .with_span(&field.ident.as_ref()),
+                ),
+                _ => {}
+            }
+        }
+
+        for field in fields.iter() {
+            errors.handle(field.option_description());
+            errors.handle(field.option_localizations());
+            errors.handle(field.command_option().and_then(|option| {
+                let (CommandOption::Optional(option_type) | CommandOption::Required(option_type)) =
+                    option;
+
+                field.generate_option_constraints(&option_type)
+            }));
+        }
+
+        if let Err(error) = errors.finish() {
+            tokens.extend(error.write_errors());
+            return;
+        }
+
         let mut option_builders = Vec::new();
         let mut option_parsers = Vec::new();
         let mut field_idents = Vec::new();
@@ -167,21 +1208,76 @@ impl quote::ToTokens for Command {
             field_idents.push(field.ident.as_ref());
         }
 
+        let has_autocomplete = fields.iter().any(|field| field.autocomplete.is_present());
+        let dispatch_autocomplete = has_autocomplete.then(|| {
+            quote! {
+                const HAS_AUTOCOMPLETE: bool = true;
+
+                async fn dispatch_autocomplete(
+                    context: &bouncer_framework::Context,
+                    interaction: &twilight_model::application::interaction::Interaction,
+                    options: &[twilight_model::application::interaction::application_command::CommandDataOption],
+                    resolved: Option<&twilight_model::application::interaction::InteractionDataResolved>,
+                ) -> Result<
+                    Vec<twilight_model::application::command::CommandOptionChoice>,
+                    bouncer_framework::command::CommandError,
+                > {
+                    let (partial, focused) = <Self as bouncer_framework::command::AutocompleteOptions>::parse_partial_options(
+                        options,
+                        resolved,
+                    )?;
+
+                    Ok(<Self as bouncer_framework::command::Autocomplete>::autocomplete(
+                        context,
+                        interaction,
+                        partial,
+                        focused,
+                    )
+                    .await?)
+                }
+            }
+        });
+
         tokens.extend(quote! {
+            #[async_trait::async_trait]
             impl bouncer_framework::command::CommandData for #ident {
                 const COMMAND_NAME: &'static str = #command_name;
                 const COMMAND_DESCRIPTION: &'static str = #command_description;
+                #defer_ephemeral
 
                 fn command() -> twilight_model::application::command::Command {
                     Self::command_builder()
+                        #command_builder
+                        #command_localizations
                         #(#option_builders)*
                         .build()
                 }
+
+                #dispatch_autocomplete
+            }
+
+            impl bouncer_framework::command::SubCommand for #ident {
+                fn subcommand() -> twilight_util::builder::command::SubCommandBuilder {
+                    twilight_util::builder::command::SubCommandBuilder::new(
+                        <Self as bouncer_framework::command::CommandData>::COMMAND_NAME,
+                        <Self as bouncer_framework::command::CommandData>::COMMAND_DESCRIPTION,
+                    )
+                    #command_localizations
+                    #(#option_builders)*
+                }
+            }
+
+            impl bouncer_framework::command::NestedCommand for #ident {
+                fn command_option() -> twilight_model::application::command::CommandOption {
+                    <Self as bouncer_framework::command::SubCommand>::subcommand().build()
+                }
             }
 
             impl bouncer_framework::command::CommandOptions for #ident {
+                #[allow(unused_variables)]
                 fn parse_options(
                     options: &[twilight_model::application::interaction::application_command::CommandDataOption],
+                    resolved: Option<&twilight_model::application::interaction::InteractionDataResolved>,
                 ) -> Result<Self, bouncer_framework::command::CommandOptionsError>
                 {
                     #(#option_parsers)*
@@ -192,7 +1288,71 @@ impl quote::ToTokens for Command {
                 }
             }
         });
+
+        if has_autocomplete {
+            let vis = &self.vis;
+            let partial_ident = format_ident!("{ident}Partial");
+            let partial_doc =
+                format!("Options of [`{ident}`], any of which may not have been filled in yet.");
+            let partial_types = fields.iter().map(CommandOptionField::partial_type);
+            let partial_parsers = fields
+                .iter()
+                .map(CommandOptionField::generate_partial_option_parsers);
+
+            tokens.extend(quote! {
+                #[doc = #partial_doc]
+                #vis struct #partial_ident {
+                    #(#vis #field_idents: #partial_types),*
+                }
+
+                impl bouncer_framework::command::AutocompleteOptions for #ident {
+                    type Partial = #partial_ident;
+
+                    #[allow(unused_variables)]
+                    fn parse_partial_options(
+                        options: &[twilight_model::application::interaction::application_command::CommandDataOption],
+                        resolved: Option<&twilight_model::application::interaction::InteractionDataResolved>,
+                    ) -> Result<
+                        (Self::Partial, bouncer_framework::command::FocusedOption),
+                        bouncer_framework::command::CommandOptionsError,
+                    > {
+                        let focused = bouncer_framework::command::parse_focused_option(options)?;
+
+                        #(#partial_parsers)*
+
+                        Ok((
+                            #partial_ident {
+                                #(#field_idents),*
+                            },
+                            focused,
+                        ))
+                    }
+                }
+            });
+        }
+    }
+}
+
+/// An option's localized names and descriptions, paired with their locales.
+type OptionLocalizations = (Vec<(&'static str, String)>, Vec<(&'static str, String)>);
+
+fn generate_localization_builder(
+    name_localizations: &[(&str, String)],
+    description_localizations: &[(&str, String)],
+) -> proc_macro2::TokenStream {
+    let mut builder = proc_macro2::TokenStream::new();
+
+    if !name_localizations.is_empty() {
+        let name_localizations = localizations_tokens(name_localizations);
+        builder.extend(quote!(.name_localizations(#name_localizations)));
+    }
+
+    if !description_localizations.is_empty() {
+        let description_localizations = localizations_tokens(description_localizations);
+        builder.extend(quote!(.description_localizations(#description_localizations)));
     }
+
+    builder
 }
 
 fn validate_command_chat_input_name(name: &str) -> darling::Result<()> {
@@ -202,6 +1362,13 @@ fn validate_command_chat_input_name(name: &str) -> darling::Result<()> {
     }
 }
 
+fn validate_command_name(name: &str) -> darling::Result<()> {
+    match command::name(name) {
+        Ok(()) => Ok(()),
+        Err(error) => Err(darling::Error::custom(error)),
+    }
+}
+
 fn validate_option_name(name: &str) -> darling::Result<()> {
     match command::option_name(name) {
         Ok(()) => Ok(()),
@@ -216,7 +1383,211 @@ fn validate_command_description(description: &str) -> darling::Result<()> {
     }
 }
 
-fn extract_path_segment(ty: &syn::Type) -> Result<&syn::PathSegment, syn::Error> {
+/// Discord's limits on `min_length` and `max_length` of string options,
+/// which twilight-validate doesn't export.
+const OPTION_MIN_LENGTH_MAX: u16 = 6000;
+const OPTION_MAX_LENGTH_MIN: u16 = 1;
+const OPTION_MAX_LENGTH_MAX: u16 = 6000;
+
+fn validate_option_length_range(
+    min_length: Option<u16>,
+    max_length: Option<u16>,
+) -> darling::Result<()> {
+    if min_length.is_some_and(|length| length > OPTION_MIN_LENGTH_MAX) {
+        return Err(darling::Error::custom(format!(
+            "`min_length` must be at most {OPTION_MIN_LENGTH_MAX}"
+        )));
+    }
+
+    if max_length
+        .is_some_and(|length| !(OPTION_MAX_LENGTH_MIN..=OPTION_MAX_LENGTH_MAX).contains(&length))
+    {
+        return Err(darling::Error::custom(format!(
+            "`max_length` must be between {OPTION_MAX_LENGTH_MIN} and {OPTION_MAX_LENGTH_MAX}"
+        )));
+    }
+
+    if let (Some(min_length), Some(max_length)) = (min_length, max_length) {
+        if min_length > max_length {
+            return Err(darling::Error::custom(
+                "`min_length` must not be greater than `max_length`",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_permission(path: &syn::Path) -> darling::Result<()> {
+    match path.get_ident() {
+        Some(ident)
+            if twilight_model::guild::Permissions::from_name(&ident.to_string()).is_some() =>
+        {
+            Ok(())
+        }
+        _ => Err(darling::Error::custom("Unknown permission").with_span(path)),
+    }
+}
+
+fn validate_context(path: &syn::Path) -> darling::Result<()> {
+    validate_variant(path, "context", &["Guild", "BotDm", "PrivateChannel"])
+}
+
+fn validate_integration_type(path: &syn::Path) -> darling::Result<()> {
+    validate_variant(path, "integration type", &["GuildInstall", "UserInstall"])
+}
+
+/// Looks up a channel type by its variant name, out of the ones twilight
+/// knows about.
+fn parse_channel_type(path: &syn::Path) -> darling::Result<ChannelType> {
+    let channel_types = (0..=u8::MAX)
+        .map(ChannelType::from)
+        .filter(|channel_type| !matches!(channel_type, ChannelType::Unknown(_)))
+        .collect::<Vec<_>>();
+
+    channel_types
+        .iter()
+        .copied()
+        .find(|channel_type| path.is_ident(channel_type.name()))
+        .ok_or_else(|| {
+            let names = channel_types
+                .iter()
+                .map(|channel_type| channel_type.name())
+                .collect::<Vec<_>>()
+                .join(", ");
+            darling::Error::custom(format!("Unknown channel type, expected one of {names}"))
+                .with_span(path)
+        })
+}
+
+fn validate_variant(path: &syn::Path, kind: &str, variants: &[&str]) -> darling::Result<()> {
+    match path.get_ident() {
+        Some(ident) if variants.contains(&&*ident.to_string()) => Ok(()),
+        _ => {
+            let variants = variants.join(", ");
+            Err(
+                darling::Error::custom(format!("Unknown {kind}, expected one of {variants}"))
+                    .with_span(path),
+            )
+        }
+    }
+}
+
+/// Discord's bound on integer and number option values, either way, which
+/// twilight-validate doesn't check.
+pub const OPTION_VALUE_MAX: i64 = 1 << 53;
+
+/// Checks a value is within [`OPTION_VALUE_MAX`] either way.
+pub fn validate_option_value(value: i64) -> darling::Result<()> {
+    if (-OPTION_VALUE_MAX..=OPTION_VALUE_MAX).contains(&value) {
+        Ok(())
+    } else {
+        Err(darling::Error::custom(format!(
+            "Values must be between -{OPTION_VALUE_MAX} and {OPTION_VALUE_MAX}"
+        )))
+    }
+}
+
+/// Splits an optionally negated literal into whether it's negated and the
+/// literal.
+fn parse_signed_literal(value: &syn::Expr) -> Option<(bool, &syn::Lit)> {
+    match value {
+        syn::Expr::Lit(syn::ExprLit { lit, .. }) => Some((false, lit)),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => match &**expr {
+            syn::Expr::Lit(syn::ExprLit { lit, .. }) => Some((true, lit)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn parse_integer_constraint(value: Option<&syn::Expr>) -> darling::Result<Option<i64>> {
+    value
+        .map(|value| {
+            let int = match parse_signed_literal(value) {
+                Some((negative, syn::Lit::Int(int))) => {
+                    let int = int.base10_parse::<i64>()?;
+                    if negative { -int } else { int }
+                }
+                _ => return Err(darling::Error::custom("Expected an integer").with_span(value)),
+            };
+
+            validate_option_value(int)
+                .map(|()| int)
+                .map_err(|error| error.with_span(value))
+        })
+        .transpose()
+}
+
+fn parse_number_constraint(value: Option<&syn::Expr>) -> darling::Result<Option<f64>> {
+    const NUMBER_MAX: f64 = OPTION_VALUE_MAX as f64;
+
+    value
+        .map(|value| {
+            let (negative, number) = match parse_signed_literal(value) {
+                Some((negative, syn::Lit::Int(number))) => {
+                    (negative, number.base10_parse::<f64>()?)
+                }
+                Some((negative, syn::Lit::Float(number))) => {
+                    (negative, number.base10_parse::<f64>()?)
+                }
+                _ => return Err(darling::Error::custom("Expected a number").with_span(value)),
+            };
+            let number = if negative { -number } else { number };
+
+            if (-NUMBER_MAX..=NUMBER_MAX).contains(&number) {
+                Ok(number)
+            } else {
+                Err(darling::Error::custom(format!(
+                    "Values must be between -{OPTION_VALUE_MAX} and {OPTION_VALUE_MAX}"
+                ))
+                .with_span(value))
+            }
+        })
+        .transpose()
+}
+
+fn validate_option_value_range(
+    min_value: Option<CommandOptionValue>,
+    max_value: Option<CommandOptionValue>,
+) -> darling::Result<()> {
+    let reversed = match (min_value, max_value) {
+        (
+            Some(CommandOptionValue::Integer(min_value)),
+            Some(CommandOptionValue::Integer(max_value)),
+        ) => min_value > max_value,
+        (
+            Some(CommandOptionValue::Number(min_value)),
+            Some(CommandOptionValue::Number(max_value)),
+        ) => min_value > max_value,
+        _ => false,
+    };
+
+    if reversed {
+        return Err(darling::Error::custom(
+            "`min_value` must not be greater than `max_value`",
+        ));
+    }
+
+    Ok(())
+}
+
+fn option_value_tokens(value: CommandOptionValue) -> proc_macro2::TokenStream {
+    match value {
+        CommandOptionValue::Integer(value) => quote!(#value),
+        CommandOptionValue::Number(value) => quote!(#value),
+    }
+}
+
+pub fn optional_tokens(value: Option<&impl quote::ToTokens>) -> proc_macro2::TokenStream {
+    value.map_or_else(|| quote!(None), |value| quote!(Some(#value)))
+}
+
+pub fn extract_path_segment(ty: &syn::Type) -> Result<&syn::PathSegment, syn::Error> {
     let span = ty.span();
 
     let syn::Type::Path(type_path) = ty else {
@@ -226,10 +1597,21 @@ fn extract_path_segment(ty: &syn::Type) -> Result<&syn::PathSegment, syn::Error>
     type_path
         .path
         .segments
-        .first()
+        .last()
         .ok_or_else(|| syn::Error::new(span, "Missing type name"))
 }
 
+pub fn extract_generic_argument(path_segment: &syn::PathSegment) -> Option<&syn::Type> {
+    let syn::PathArguments::AngleBracketed(args) = &path_segment.arguments else {
+        return None;
+    };
+
+    match args.args.first() {
+        Some(syn::GenericArgument::Type(inner_type)) => Some(inner_type),
+        _ => None,
+    }
+}
+
 impl TryFrom<&syn::Type> for CommandOption {
     type Error = darling::Error;
 
@@ -238,10 +1620,10 @@ impl TryFrom<&syn::Type> for CommandOption {
         let ident = &path_segment.ident;
 
         if ident == "Option" {
-            if let syn::PathArguments::AngleBracketed(args) = &path_segment.arguments {
-                if let Some(syn::GenericArgument::Type(inner_type)) = args.args.first() {
-                    return Ok(Self::Optional(CommandOptionType::try_from(inner_type)?));
-                }
+            if let Some(inner_type) = extract_generic_argument(path_segment) {
+                return Ok(Self::Optional(CommandOptionValueType::try_from(
+                    inner_type,
+                )?));
             }
 
             return Err(
@@ -249,24 +1631,69 @@ impl TryFrom<&syn::Type> for CommandOption {
             );
         }
 
-        Ok(Self::Required(CommandOptionType::try_from(value)?))
+        Ok(Self::Required(CommandOptionValueType::try_from(value)?))
     }
 }
 
-impl TryFrom<&syn::Type> for CommandOptionType {
+impl TryFrom<&syn::Type> for CommandOptionValueType {
     type Error = darling::Error;
 
     fn try_from(value: &syn::Type) -> Result<Self, Self::Error> {
         let path_segment = extract_path_segment(value)?;
         let type_name = path_segment.ident.to_string();
 
-        match &type_name[..] {
-            "bool" => Ok(Self::Boolean),
-            "String" => Ok(Self::String),
-            _ => Err(
-                darling::Error::custom(format!("Unsupported option type `{type_name}`"))
-                    .with_span(&path_segment.ident.span()),
+        let option = |kind, resolved| Self::Value { kind, resolved };
+
+        Ok(match &type_name[..] {
+            "bool" => option(CommandOptionType::Boolean, None),
+            "String" => option(CommandOptionType::String, None),
+            "i64" => option(CommandOptionType::Integer, None),
+            "f64" => option(CommandOptionType::Number, None),
+            "Id" => option(Self::try_from_id_marker(path_segment)?, None),
+            "User" => option(CommandOptionType::User, Some(ResolvedOptionData::Users)),
+            "InteractionMember" => {
+                option(CommandOptionType::User, Some(ResolvedOptionData::Members))
+            }
+            "InteractionChannel" => option(
+                CommandOptionType::Channel,
+                Some(ResolvedOptionData::Channels),
             ),
+            "Role" => option(CommandOptionType::Role, Some(ResolvedOptionData::Roles)),
+            "Attachment" => option(
+                CommandOptionType::Attachment,
+                Some(ResolvedOptionData::Attachments),
+            ),
+            _ => {
+                return Err(darling::Error::custom(format!(
+                    "Unsupported option type `{type_name}`, types implementing `CommandChoice` \
+                     need `#[option(choice)]`"
+                ))
+                .with_span(&path_segment.ident.span()));
+            }
+        })
+    }
+}
+
+impl CommandOptionValueType {
+    fn try_from_id_marker(path_segment: &syn::PathSegment) -> darling::Result<CommandOptionType> {
+        let marker = extract_generic_argument(path_segment)
+            .ok_or_else(|| {
+                darling::Error::custom("Invalid Id type arguments")
+                    .with_span(&path_segment.ident.span())
+            })
+            .and_then(|marker| extract_path_segment(marker).map_err(darling::Error::from))?;
+        let marker_name = marker.ident.to_string();
+
+        match &marker_name[..] {
+            "UserMarker" => Ok(CommandOptionType::User),
+            "ChannelMarker" => Ok(CommandOptionType::Channel),
+            "RoleMarker" => Ok(CommandOptionType::Role),
+            "GenericMarker" => Ok(CommandOptionType::Mentionable),
+            "AttachmentMarker" => Ok(CommandOptionType::Attachment),
+            _ => Err(darling::Error::custom(format!(
+                "Unsupported option ID type `Id<{marker_name}>`"
+            ))
+            .with_span(&marker.ident.span())),
         }
     }
 }
@@ -276,21 +1703,39 @@ impl core::fmt::Display for CommandOptionType {
         match self {
             Self::Boolean => write!(f, "Boolean"),
             Self::String => write!(f, "String"),
+            Self::Integer => write!(f, "Integer"),
+            Self::Number => write!(f, "Number"),
+            Self::User => write!(f, "User"),
+            Self::Channel => write!(f, "Channel"),
+            Self::Role => write!(f, "Role"),
+            Self::Mentionable => write!(f, "Mentionable"),
+            Self::Attachment => write!(f, "Attachment"),
+        }
+    }
+}
+
+impl From<CommandOptionType> for twilight_model::application::command::CommandOptionType {
+    fn from(kind: CommandOptionType) -> Self {
+        match kind {
+            CommandOptionType::Boolean => Self::Boolean,
+            CommandOptionType::String => Self::String,
+            CommandOptionType::Integer => Self::Integer,
+            CommandOptionType::Number => Self::Number,
+            CommandOptionType::User => Self::User,
+            CommandOptionType::Channel => Self::Channel,
+            CommandOptionType::Role => Self::Role,
+            CommandOptionType::Mentionable => Self::Mentionable,
+            CommandOptionType::Attachment => Self::Attachment,
         }
     }
 }
 
 impl quote::ToTokens for CommandOptionType {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        match self {
-            Self::Boolean => {
-                quote!(twilight_model::application::interaction::application_command::CommandOptionValue::Boolean)
-            }
-            Self::String => {
-                quote!(twilight_model::application::interaction::application_command::CommandOptionValue::String)
-            }
-        }
-        .to_tokens(tokens);
+        let variant = format_ident!("{self}");
+
+        quote!(twilight_model::application::interaction::application_command::CommandOptionValue::#variant)
+            .to_tokens(tokens);
     }
 }
 
@@ -299,3 +1744,341 @@ impl quote::IdentFragment for CommandOptionType {
         write!(f, "{self}")
     }
 }
+
+impl quote::ToTokens for CommandKind {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match self {
+            Self::ChatInput => quote!(twilight_model::application::command::CommandType::ChatInput),
+            Self::User => quote!(twilight_model::application::command::CommandType::User),
+            Self::Message => quote!(twilight_model::application::command::CommandType::Message),
+        }
+        .to_tokens(tokens);
+    }
+}
+
+impl quote::ToTokens for ResolvedOptionData {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match self {
+            Self::Users => quote!(users),
+            Self::Members => quote!(members),
+            Self::Channels => quote!(channels),
+            Self::Roles => quote!(roles),
+            Self::Attachments => quote!(attachments),
+            Self::Messages => quote!(messages),
+        }
+        .to_tokens(tokens);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::ToTokens as _;
+
+    use super::*;
+
+    fn expand(input: proc_macro2::TokenStream) -> String {
+        let input = syn::parse2::<syn::DeriveInput>(input).unwrap();
+
+        match Command::from_derive_input(&input) {
+            Ok(command) => command.to_token_stream(),
+            Err(error) => error.write_errors(),
+        }
+        .to_string()
+    }
+
+    #[test]
+    fn test_command_options() {
+        let expanded = expand(quote! {
+            #[command(name = "ban", description = "Bans a user")]
+            struct Ban {
+                #[option(description = "The user to ban")]
+                user: Id<UserMarker>,
+                #[option(description = "Why they're banned")]
+                reason: Option<String>,
+            }
+        });
+
+        assert!(!expanded.contains("compile_error"));
+        assert!(expanded.contains("UserBuilder :: new (\"user\" , \"The user to ban\")"));
+        assert!(expanded.contains("StringBuilder :: new (\"reason\" , \"Why they're banned\")"));
+    }
+
+    #[test]
+    fn test_required_option_after_optional() {
+        let expanded = expand(quote! {
+            #[command(name = "ban", description = "Bans a user")]
+            struct Ban {
+                #[option(description = "Why they're banned")]
+                reason: Option<String>,
+                #[option(description = "The user to ban")]
+                user: Id<UserMarker>,
+            }
+        });
+
+        assert!(expanded.contains("Required options must come before optional ones"));
+    }
+
+    #[test]
+    fn test_too_many_options() {
+        let fields = (0..=command::OPTIONS_LIMIT).map(|index| {
+            let ident = format_ident!("option_{index}");
+
+            quote! {
+                #[option(description = "An option")]
+                #ident: Option<String>
+            }
+        });
+        let expanded = expand(quote! {
+            #[command(name = "many", description = "Has many options")]
+            struct Many {
+                #(#fields),*
+            }
+        });
+
+        assert!(expanded.contains("A command can have at most 25 options"));
+    }
+
+    #[test]
+    fn test_subcommands() {
+        let expanded = expand(quote! {
+            #[command(name = "role", description = "Manages roles")]
+            enum Role {
+                Add(RoleAdd),
+                Remove(RoleRemove),
+            }
+        });
+
+        assert!(!expanded.contains("compile_error"));
+        assert!(expanded.contains(
+            ". option (< RoleAdd as bouncer_framework :: command :: NestedCommand > :: command_option ())"
+        ));
+        assert!(expanded.contains("impl bouncer_framework :: command :: SubCommandGroup for Role"));
+    }
+
+    #[test]
+    fn test_too_many_subcommands() {
+        let variants = (0..=command::OPTIONS_LIMIT).map(|index| {
+            let ident = format_ident!("Subcommand{index}");
+
+            quote!(#ident(#ident))
+        });
+        let expanded = expand(quote! {
+            #[command(name = "many", description = "Has many subcommands")]
+            enum Many {
+                #(#variants),*
+            }
+        });
+
+        assert!(expanded.contains("A command can have at most 25 subcommands"));
+    }
+
+    #[test]
+    fn test_option_constraints() {
+        let expanded = expand(quote! {
+            #[command(name = "purge", description = "Deletes messages")]
+            struct Purge {
+                #[option(description = "How many", min_value = 1, max_value = 100)]
+                count: i64,
+                #[option(description = "Why", min_length = 2, max_length = 50)]
+                reason: String,
+                #[option(description = "Where", channel_types(GuildText))]
+                channel: Option<Id<ChannelMarker>>,
+            }
+        });
+
+        assert!(!expanded.contains("compile_error"));
+        assert!(expanded.contains(". min_value (1i64) . max_value (100i64)"));
+        assert!(expanded.contains(". min_length (2u16) . max_length (50u16)"));
+        assert!(
+            expanded.contains(
+                ". channel_types ([twilight_model :: channel :: ChannelType :: GuildText])"
+            )
+        );
+    }
+
+    #[test]
+    fn test_reject_mismatched_constraints() {
+        let expanded = expand(quote! {
+            #[command(name = "purge", description = "Deletes messages")]
+            struct Purge {
+                #[option(description = "How many", min_length = 1)]
+                count: i64,
+                #[option(description = "Why", max_value = 10)]
+                reason: String,
+                #[option(description = "Who", channel_types(GuildText))]
+                user: Id<UserMarker>,
+            }
+        });
+
+        assert!(
+            expanded.contains("`min_length` and `max_length` are only supported on string options")
+        );
+        assert!(expanded.contains(
+            "`min_value` and `max_value` are only supported on integer and number options"
+        ));
+        assert!(expanded.contains("`channel_types` is only supported on channel options"));
+    }
+
+    #[test]
+    fn test_reject_invalid_constraints() {
+        let expanded = expand(quote! {
+            #[command(name = "purge", description = "Deletes messages")]
+            struct Purge {
+                #[option(description = "How many", min_value = 10, max_value = 1)]
+                count: i64,
+                #[option(description = "Where", channel_types(Nowhere))]
+                channel: Id<ChannelMarker>,
+            }
+        });
+
+        assert!(expanded.contains("`min_value` must not be greater than `max_value`"));
+        assert!(expanded.contains("Unknown channel type"));
+    }
+
+    #[test]
+    fn test_autocomplete() {
+        let expanded = expand(quote! {
+            #[command(name = "tag", description = "Shows a tag")]
+            struct Tag {
+                #[option(description = "The tag", autocomplete)]
+                name: String,
+            }
+        });
+
+        assert!(!expanded.contains("compile_error"));
+        assert!(expanded.contains("const HAS_AUTOCOMPLETE : bool = true ;"));
+        assert!(expanded.contains(". autocomplete (true)"));
+    }
+
+    #[test]
+    fn test_reject_autocomplete_on_unsupported_options() {
+        let expanded = expand(quote! {
+            #[command(name = "tag", description = "Shows a tag")]
+            struct Tag {
+                #[option(description = "Whether it's raw", autocomplete)]
+                raw: bool,
+            }
+        });
+
+        assert!(
+            expanded
+                .contains("`autocomplete` is only supported on string, integer and number options")
+        );
+    }
+
+    #[test]
+    fn test_context_menu_command() {
+        let expanded = expand(quote! {
+            #[command(name = "Report message", kind = "message")]
+            struct Report {
+                message: Message,
+            }
+        });
+
+        assert!(!expanded.contains("compile_error"));
+        assert!(expanded.contains(
+            "const COMMAND_TYPE : twilight_model :: application :: command :: CommandType = twilight_model :: application :: command :: CommandType :: Message ;"
+        ));
+        assert!(expanded.contains("resolved . messages . get (& id) . cloned ()"));
+    }
+
+    #[test]
+    fn test_reject_context_menu_misuse() {
+        let expanded = expand(quote! {
+            #[command(name = "Ban", kind = "user")]
+            struct Ban {
+                #[option(description = "Why")]
+                reason: String,
+                channel: Id<ChannelMarker>,
+            }
+        });
+
+        assert!(expanded.contains("Context menu commands can't have options"));
+        assert!(expanded.contains("Unsupported target type `Id`"));
+
+        let expanded = expand(quote! {
+            #[command(name = "Ban", kind = "user", description = "Bans a user")]
+            struct Ban;
+        });
+
+        assert!(expanded.contains("Context menu commands can't have a description"));
+    }
+
+    #[test]
+    fn test_command_permissions() {
+        let expanded = expand(quote! {
+            #[command(
+                name = "ban",
+                description = "Bans a user",
+                default_member_permissions(BAN_MEMBERS, KICK_MEMBERS),
+                dm_permission = false,
+                nsfw
+            )]
+            struct Ban;
+        });
+
+        assert!(!expanded.contains("compile_error"));
+        assert!(expanded.contains(
+            ". default_member_permissions (twilight_model :: guild :: Permissions :: BAN_MEMBERS | twilight_model :: guild :: Permissions :: KICK_MEMBERS)"
+        ));
+        assert!(expanded.contains(
+            ". contexts ([twilight_model :: application :: interaction :: InteractionContextType :: Guild])"
+        ));
+        assert!(expanded.contains(". nsfw (true)"));
+    }
+
+    #[test]
+    fn test_reject_invalid_permissions() {
+        let expanded = expand(quote! {
+            #[command(
+                name = "ban",
+                description = "Bans a user",
+                default_member_permissions(BAN_EVERYONE),
+                dm_permission = true,
+                contexts(Guild)
+            )]
+            struct Ban;
+        });
+
+        assert!(expanded.contains("Unknown permission"));
+        assert!(expanded.contains("`dm_permission` can't be combined with `contexts`"));
+    }
+
+    #[test]
+    fn test_command_localizations() {
+        let expanded = expand(quote! {
+            #[command(
+                name = "ban",
+                description = "Bans a user",
+                name_localizations(de = "Bannen"),
+                description_localizations(de = "Bannt einen Nutzer")
+            )]
+            struct Ban {
+                #[option(description = "The user", name_localizations(fr = "utilisateur"))]
+                user: Id<UserMarker>,
+            }
+        });
+
+        assert!(!expanded.contains("compile_error"));
+        assert!(expanded.contains("(\"de\" , \"bannen\")"));
+        assert!(expanded.contains("(\"de\" , \"Bannt einen Nutzer\")"));
+        assert!(expanded.contains("(\"fr\" , \"utilisateur\")"));
+    }
+
+    #[test]
+    fn test_reject_invalid_localizations() {
+        let expanded = expand(quote! {
+            #[command(name = "ban", description = "Bans a user", name_localizations(xx = "ban"))]
+            struct Ban;
+        });
+
+        assert!(expanded.contains("Unknown locale"));
+
+        let expanded = expand(quote! {
+            #[command(name = "Ban", kind = "user", description_localizations(de = "Bannt"))]
+            struct Ban;
+        });
+
+        assert!(expanded.contains("Context menu commands can't have a description"));
+    }
+}
@@ -0,0 +1,184 @@
+use darling::{FromDeriveInput, FromField, FromVariant};
+use quote::quote;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(supports(enum_newtype))]
+pub struct Commands {
+    ident: syn::Ident,
+    data: darling::ast::Data<CommandsVariant, darling::util::Ignored>,
+}
+
+#[derive(Debug, FromVariant)]
+#[darling(attributes(command))]
+pub struct CommandsVariant {
+    ident: syn::Ident,
+    fields: darling::ast::Fields<CommandsVariantField>,
+}
+
+#[derive(Debug, FromField)]
+pub struct CommandsVariantField {
+    ty: syn::Type,
+}
+
+impl CommandsVariant {
+    pub const fn ident(&self) -> &syn::Ident {
+        &self.ident
+    }
+
+    pub fn command_type(&self) -> &syn::Type {
+        // `supports(enum_newtype)` guarantees exactly one field.
+        &self.fields.fields[0].ty
+    }
+}
+
+impl quote::ToTokens for Commands {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let ident = &self.ident;
+
+        let darling::ast::Data::Enum(variants) = &self.data else {
+            unreachable!()
+        };
+
+        let variant_idents = variants
+            .iter()
+            .map(CommandsVariant::ident)
+            .collect::<Vec<_>>();
+        let command_types = variants
+            .iter()
+            .map(CommandsVariant::command_type)
+            .collect::<Vec<_>>();
+
+        tokens.extend(quote! {
+            #[async_trait::async_trait]
+            impl bouncer_framework::command::Commands for #ident {
+                fn all_commands() -> Vec<twilight_model::application::command::Command> {
+                    vec![
+                        #(<#command_types as bouncer_framework::command::CommandData>::command()),*
+                    ]
+                }
+
+                fn parse_from_command_data(
+                    data: &twilight_model::application::interaction::application_command::CommandData,
+                ) -> Result<Self, bouncer_framework::command::CommandError> {
+                    #(
+                        if data.kind == <#command_types as bouncer_framework::command::CommandData>::COMMAND_TYPE
+                            && data.name == <#command_types as bouncer_framework::command::CommandData>::COMMAND_NAME
+                        {
+                            return Ok(Self::#variant_idents(
+                                <#command_types as bouncer_framework::command::CommandOptions>::parse_command_data(data)?,
+                            ));
+                        }
+                    )*
+
+                    Err(bouncer_framework::command::CommandError::UnknownCommand(data.name.clone()))
+                }
+
+                #[allow(unused_variables)]
+                fn defer_ephemeral(
+                    data: &twilight_model::application::interaction::application_command::CommandData,
+                ) -> Option<bool> {
+                    #(
+                        if data.kind == <#command_types as bouncer_framework::command::CommandData>::COMMAND_TYPE
+                            && data.name == <#command_types as bouncer_framework::command::CommandData>::COMMAND_NAME
+                        {
+                            return <#command_types as bouncer_framework::command::CommandData>::defer_ephemeral(&data.options);
+                        }
+                    )*
+
+                    None
+                }
+
+                async fn execute(
+                    &self,
+                    context: &bouncer_framework::Context,
+                    interaction: &twilight_model::application::interaction::Interaction,
+                ) -> Result<(), bouncer_framework::command::CommandExecuteError> {
+                    match *self {
+                        #(
+                            Self::#variant_idents(ref command) => {
+                                bouncer_framework::command::Command::execute(command, context, interaction).await
+                            }
+                        )*
+                    }
+                }
+
+                #[allow(unused_variables)]
+                async fn autocomplete(
+                    context: &bouncer_framework::Context,
+                    interaction: &twilight_model::application::interaction::Interaction,
+                    name: &str,
+                    options: &[twilight_model::application::interaction::application_command::CommandDataOption],
+                    resolved: Option<&twilight_model::application::interaction::InteractionDataResolved>,
+                ) -> Result<
+                    Vec<twilight_model::application::command::CommandOptionChoice>,
+                    bouncer_framework::command::CommandError,
+                > {
+                    #(
+                        if <#command_types as bouncer_framework::command::CommandData>::COMMAND_TYPE
+                            == twilight_model::application::command::CommandType::ChatInput
+                            && name == <#command_types as bouncer_framework::command::CommandData>::COMMAND_NAME
+                        {
+                            return <#command_types as bouncer_framework::command::CommandData>::dispatch_autocomplete(
+                                context,
+                                interaction,
+                                options,
+                                resolved,
+                            )
+                            .await;
+                        }
+                    )*
+
+                    Err(bouncer_framework::command::CommandError::MissingAutocomplete(name.to_string()))
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::ToTokens as _;
+
+    use super::*;
+
+    #[test]
+    fn test_route_variants() {
+        let input = syn::parse_quote! {
+            enum Commands {
+                Ban(BanCommand),
+                Kick(KickCommand),
+            }
+        };
+        let expanded = Commands::from_derive_input(&input)
+            .unwrap()
+            .to_token_stream()
+            .to_string();
+
+        assert!(expanded.contains(
+            "return Ok (Self :: Ban (< BanCommand as bouncer_framework :: command :: CommandOptions > :: parse_command_data (data) ? ,))"
+        ));
+        assert!(expanded.contains(
+            "Self :: Kick (ref command) => { bouncer_framework :: command :: Command :: execute (command , context , interaction) . await }"
+        ));
+    }
+
+    #[test]
+    fn test_reject_non_newtype_variants() {
+        let input = syn::parse_quote! {
+            enum Commands {
+                Ban { command: BanCommand },
+            }
+        };
+
+        assert!(Commands::from_derive_input(&input).is_err());
+    }
+
+    #[test]
+    fn test_reject_structs() {
+        let input = syn::parse_quote! {
+            struct Commands(BanCommand);
+        };
+
+        assert!(Commands::from_derive_input(&input).is_err());
+    }
+}
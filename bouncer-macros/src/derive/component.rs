@@ -0,0 +1,175 @@
+use darling::{FromDeriveInput, FromField};
+use quote::quote;
+
+/// The maximum length of a custom ID.
+const CUSTOM_ID_LENGTH_MAX: usize = 100;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(component), supports(struct_named, struct_unit))]
+pub struct Component {
+    ident: syn::Ident,
+    data: darling::ast::Data<darling::util::Ignored, ComponentField>,
+
+    id: Option<String>,
+    version: Option<u32>,
+}
+
+#[derive(Debug, FromField)]
+pub struct ComponentField {
+    ident: Option<syn::Ident>,
+}
+
+impl Component {
+    fn component_id(&self) -> darling::Result<String> {
+        let id = self
+            .id
+            .clone()
+            .unwrap_or_else(|| self.ident.to_string().to_lowercase());
+        let version = self.version.unwrap_or_default();
+        let prefix = format!("{id}:{version}");
+
+        if id.is_empty() || id.contains(':') {
+            return Err(darling::Error::custom(
+                "Component ID must be non-empty and can't contain `:`",
+            )
+            .with_span(&self.ident));
+        }
+        if prefix.chars().count() > CUSTOM_ID_LENGTH_MAX {
+            return Err(darling::Error::custom(format!(
+                "Component ID and version must be at most {CUSTOM_ID_LENGTH_MAX} characters long"
+            ))
+            .with_span(&self.ident));
+        }
+
+        Ok(id)
+    }
+}
+
+impl quote::ToTokens for Component {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let ident = &self.ident;
+
+        let component_id = match self.component_id() {
+            Ok(component_id) => component_id,
+            Err(error) => {
+                tokens.extend(error.write_errors());
+                return;
+            }
+        };
+        let component_version = self.version.unwrap_or_default();
+
+        let darling::ast::Data::Struct(fields) = &self.data else {
+            unreachable!()
+        };
+
+        let field_idents = fields
+            .iter()
+            .filter_map(|field| field.ident.as_ref())
+            .collect::<Vec<_>>();
+        let field_names = field_idents.iter().map(ToString::to_string);
+        let field_count = field_idents.len();
+
+        let (construct, pattern) = if fields.is_unit() {
+            (quote!(Self), quote!([]))
+        } else {
+            (
+                quote! {
+                    Self {
+                        #(
+                            #field_idents: bouncer_framework::component::decode_field(
+                                #field_names,
+                                #field_idents,
+                            )?,
+                        )*
+                    }
+                },
+                quote!([#(#field_idents),*]),
+            )
+        };
+
+        tokens.extend(quote! {
+            impl bouncer_framework::component::ComponentData for #ident {
+                const COMPONENT_ID: &'static str = #component_id;
+                const COMPONENT_VERSION: u32 = #component_version;
+
+                fn encode_fields(&self) -> Vec<String> {
+                    vec![#(bouncer_framework::component::encode_field(&self.#field_idents)),*]
+                }
+
+                fn decode_fields(
+                    fields: &[&str],
+                ) -> Result<Self, bouncer_framework::component::CustomIdError> {
+                    let #pattern = fields else {
+                        return Err(bouncer_framework::component::CustomIdError::UnexpectedFieldCount {
+                            expected: #field_count,
+                            found: fields.len(),
+                        });
+                    };
+
+                    Ok(#construct)
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::ToTokens as _;
+
+    use super::*;
+
+    fn expand(input: &syn::DeriveInput) -> String {
+        match Component::from_derive_input(input) {
+            Ok(component) => component.to_token_stream(),
+            Err(error) => error.write_errors(),
+        }
+        .to_string()
+    }
+
+    #[test]
+    fn test_component_fields() {
+        let expanded = expand(&syn::parse_quote! {
+            #[component(id = "page", version = 2)]
+            struct Page {
+                query: String,
+                page: u32,
+            }
+        });
+
+        assert!(!expanded.contains("compile_error"));
+        assert!(expanded.contains("const COMPONENT_ID : & 'static str = \"page\" ;"));
+        assert!(expanded.contains("const COMPONENT_VERSION : u32 = 2u32 ;"));
+        assert!(expanded.contains("let [query , page] = fields else"));
+        assert!(expanded.contains("expected : 2usize"));
+    }
+
+    #[test]
+    fn test_unit_component() {
+        let expanded = expand(&syn::parse_quote! {
+            struct Confirm;
+        });
+
+        assert!(!expanded.contains("compile_error"));
+        assert!(expanded.contains("const COMPONENT_ID : & 'static str = \"confirm\" ;"));
+        assert!(expanded.contains("let [] = fields else"));
+    }
+
+    #[test]
+    fn test_reject_invalid_ids() {
+        let expanded = expand(&syn::parse_quote! {
+            #[component(id = "a:b")]
+            struct Page;
+        });
+
+        assert!(expanded.contains("Component ID must be non-empty and can't contain `:`"));
+
+        let id = "a".repeat(CUSTOM_ID_LENGTH_MAX);
+        let expanded = expand(&syn::parse_quote! {
+            #[component(id = #id)]
+            struct Page;
+        });
+
+        assert!(expanded.contains("Component ID and version must be at most 100 characters long"));
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::BTreeMap;
+
+use quote::quote;
+
+/// Localized values keyed by locale, written with underscores in place of
+/// hyphens, such as `pt_br = "..."`.
+pub type Localizations = BTreeMap<syn::Ident, String>;
+
+/// The locales Discord supports.
+const LOCALES: &[&str] = &[
+    "id", "da", "de", "en-GB", "en-US", "es-ES", "es-419", "fr", "hr", "it", "lt", "hu", "nl",
+    "no", "pl", "pt-BR", "ro", "fi", "sv-SE", "vi", "tr", "cs", "el", "bg", "ru", "uk", "hi", "th",
+    "zh-CN", "ja", "zh-TW", "ko",
+];
+
+/// Validates localizations with `validate`, which returns the value to use,
+/// and pairs each value with its Discord locale.
+pub fn parse_localizations(
+    localizations: Option<&Localizations>,
+    validate: impl Fn(&str) -> darling::Result<String>,
+) -> darling::Result<Vec<(&'static str, String)>> {
+    let mut errors = darling::Error::accumulator();
+    let localizations = localizations
+        .into_iter()
+        .flatten()
+        .filter_map(|(key, value)| {
+            errors.handle(
+                parse_locale(key)
+                    .and_then(|locale| Ok((locale, validate(value)?)))
+                    .map_err(|error| error.with_span(key)),
+            )
+        })
+        .collect();
+
+    errors.finish_with(localizations)
+}
+
+/// Generates localizations as an array of `(locale, value)` pairs.
+pub fn localizations_tokens(localizations: &[(&str, String)]) -> proc_macro2::TokenStream {
+    let (locales, values): (Vec<_>, Vec<_>) = localizations.iter().cloned().unzip();
+
+    quote!([#((#locales, #values)),*])
+}
+
+fn parse_locale(key: &syn::Ident) -> darling::Result<&'static str> {
+    let key = key.to_string();
+
+    LOCALES
+        .iter()
+        .find(|locale| locale.replace('-', "_").eq_ignore_ascii_case(&key))
+        .copied()
+        .ok_or_else(|| {
+            let locales = LOCALES.join(", ");
+            darling::Error::custom(format!("Unknown locale, expected one of {locales}"))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::format_ident;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_localizations() {
+        let localizations = Localizations::from([
+            (format_ident!("de"), "Hallo".to_owned()),
+            (format_ident!("pt_br"), "Olá".to_owned()),
+            (format_ident!("EN_us"), "Hello".to_owned()),
+        ]);
+        let localizations =
+            parse_localizations(Some(&localizations), |value| Ok(value.to_uppercase())).unwrap();
+
+        assert_eq!(
+            localizations,
+            [
+                ("en-US", "HELLO".to_owned()),
+                ("de", "HALLO".to_owned()),
+                ("pt-BR", "OLÁ".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reject_unknown_locales() {
+        let localizations = Localizations::from([(format_ident!("en"), "Hello".to_owned())]);
+        let error =
+            parse_localizations(Some(&localizations), |value| Ok(value.to_owned())).unwrap_err();
+
+        assert!(error.to_string().starts_with("Unknown locale"));
+    }
+
+    #[test]
+    fn test_localizations_tokens() {
+        let tokens = localizations_tokens(&[("de", "Hallo".to_owned())]);
+
+        assert_eq!(tokens.to_string(), r#"[("de" , "Hallo")]"#);
+    }
+}
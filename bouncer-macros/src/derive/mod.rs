@@ -1 +1,6 @@
+pub mod choice;
 pub mod command;
+pub mod commands;
+pub mod component;
+pub mod localizations;
+pub mod modal;
@@ -0,0 +1,348 @@
+use darling::{FromDeriveInput, FromField, FromMeta};
+use quote::quote;
+
+use crate::derive::command::{extract_generic_argument, extract_path_segment, optional_tokens};
+
+/// Discord's limits on modals and their text inputs.
+const MODAL_CUSTOM_ID_LENGTH_MAX: usize = 100;
+const MODAL_TITLE_LENGTH_MAX: usize = 45;
+const MODAL_INPUTS_MAX: usize = 5;
+const INPUT_LABEL_LENGTH_MAX: usize = 45;
+const INPUT_PLACEHOLDER_LENGTH_MAX: usize = 100;
+const INPUT_MIN_LENGTH_MAX: u16 = 4000;
+const INPUT_MAX_LENGTH_MIN: u16 = 1;
+const INPUT_MAX_LENGTH_MAX: u16 = 4000;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(modal), supports(struct_named))]
+pub struct Modal {
+    ident: syn::Ident,
+    data: darling::ast::Data<darling::util::Ignored, ModalInput>,
+
+    id: Option<String>,
+    title: String,
+}
+
+#[derive(Debug, FromField)]
+#[darling(attributes(input))]
+pub struct ModalInput {
+    ident: Option<syn::Ident>,
+    ty: syn::Type,
+
+    label: String,
+    style: Option<ModalInputStyle>,
+    min_length: Option<u16>,
+    max_length: Option<u16>,
+    placeholder: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, FromMeta)]
+#[darling(rename_all = "snake_case")]
+pub enum ModalInputStyle {
+    #[default]
+    Short,
+    Paragraph,
+}
+
+impl Modal {
+    fn modal_id(&self) -> darling::Result<String> {
+        let id = self
+            .id
+            .clone()
+            .unwrap_or_else(|| self.ident.to_string().to_lowercase());
+
+        if id.is_empty() || id.chars().count() > MODAL_CUSTOM_ID_LENGTH_MAX {
+            return Err(darling::Error::custom(format!(
+                "Modal ID must be between 1 and {MODAL_CUSTOM_ID_LENGTH_MAX} characters long"
+            ))
+            .with_span(&self.ident));
+        }
+
+        Ok(id)
+    }
+
+    fn validate_title(&self) -> darling::Result<()> {
+        if self.title.is_empty() || self.title.chars().count() > MODAL_TITLE_LENGTH_MAX {
+            return Err(darling::Error::custom(format!(
+                "Modal title must be between 1 and {MODAL_TITLE_LENGTH_MAX} characters long"
+            ))
+            .with_span(&self.ident));
+        }
+
+        Ok(())
+    }
+}
+
+impl ModalInput {
+    fn input_id(&self) -> String {
+        // `supports(struct_named)` guarantees every field is named.
+        self.ident
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default()
+    }
+
+    /// Returns whether the input must be filled in, which is the case for
+    /// `String` fields, as opposed to `Option<String>` ones.
+    fn is_required(&self) -> darling::Result<bool> {
+        let path_segment = extract_path_segment(&self.ty)?;
+
+        let (required, ty) = if path_segment.ident == "Option" {
+            let inner_type = extract_generic_argument(path_segment)
+                .map(extract_path_segment)
+                .transpose()?;
+
+            (false, inner_type)
+        } else {
+            (true, Some(path_segment))
+        };
+
+        if ty.is_none_or(|ty| ty.ident != "String") {
+            return Err(
+                darling::Error::custom("Inputs must be a `String` or `Option<String>`")
+                    .with_span(&self.ty),
+            );
+        }
+
+        Ok(required)
+    }
+
+    fn validate(&self) -> darling::Result<()> {
+        let mut errors = darling::Error::accumulator();
+
+        if self.label.is_empty() || self.label.chars().count() > INPUT_LABEL_LENGTH_MAX {
+            errors.push(darling::Error::custom(format!(
+                "`label` must be between 1 and {INPUT_LABEL_LENGTH_MAX} characters long"
+            )));
+        }
+
+        if self
+            .placeholder
+            .as_ref()
+            .is_some_and(|placeholder| placeholder.chars().count() > INPUT_PLACEHOLDER_LENGTH_MAX)
+        {
+            errors.push(darling::Error::custom(format!(
+                "`placeholder` must be at most {INPUT_PLACEHOLDER_LENGTH_MAX} characters long"
+            )));
+        }
+
+        if self
+            .min_length
+            .is_some_and(|length| length > INPUT_MIN_LENGTH_MAX)
+        {
+            errors.push(darling::Error::custom(format!(
+                "`min_length` must be at most {INPUT_MIN_LENGTH_MAX}"
+            )));
+        }
+
+        if self
+            .max_length
+            .is_some_and(|length| !(INPUT_MAX_LENGTH_MIN..=INPUT_MAX_LENGTH_MAX).contains(&length))
+        {
+            errors.push(darling::Error::custom(format!(
+                "`max_length` must be between {INPUT_MAX_LENGTH_MIN} and {INPUT_MAX_LENGTH_MAX}"
+            )));
+        }
+
+        if let (Some(min_length), Some(max_length)) = (self.min_length, self.max_length) {
+            if min_length > max_length {
+                errors.push(darling::Error::custom(
+                    "`min_length` must not be greater than `max_length`",
+                ));
+            }
+        }
+
+        errors
+            .finish()
+            .map_err(|error| error.with_span(&self.ident.as_ref()))
+    }
+
+    fn generate_text_input(&self, required: bool) -> proc_macro2::TokenStream {
+        let input_id = self.input_id();
+        let label = &self.label;
+        let style = match self.style.unwrap_or_default() {
+            ModalInputStyle::Short => quote!(Short),
+            ModalInputStyle::Paragraph => quote!(Paragraph),
+        };
+        let min_length = optional_tokens(self.min_length.as_ref());
+        let max_length = optional_tokens(self.max_length.as_ref());
+        let placeholder = optional_tokens(
+            self.placeholder
+                .as_ref()
+                .map(|placeholder| quote!(#placeholder.to_string()))
+                .as_ref(),
+        );
+
+        quote! {
+            twilight_model::channel::message::component::TextInput {
+                custom_id: #input_id.to_string(),
+                label: #label.to_string(),
+                max_length: #max_length,
+                min_length: #min_length,
+                placeholder: #placeholder,
+                required: Some(#required),
+                style: twilight_model::channel::message::component::TextInputStyle::#style,
+                value: None,
+            }
+        }
+    }
+
+    fn generate_parser(&self, required: bool) -> proc_macro2::TokenStream {
+        let input_id = self.input_id();
+
+        if required {
+            quote!(bouncer_framework::component::modal::parse_required_input(data, #input_id)?)
+        } else {
+            quote!(bouncer_framework::component::modal::parse_optional_input(data, #input_id))
+        }
+    }
+}
+
+impl quote::ToTokens for Modal {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let ident = &self.ident;
+
+        let modal_id = match self.modal_id() {
+            Ok(modal_id) => modal_id,
+            Err(error) => {
+                tokens.extend(error.write_errors());
+                return;
+            }
+        };
+
+        let darling::ast::Data::Struct(fields) = &self.data else {
+            unreachable!()
+        };
+
+        let mut errors = darling::Error::accumulator();
+        errors.handle(self.validate_title());
+        if !(1..=MODAL_INPUTS_MAX).contains(&fields.len()) {
+            errors.push(
+                darling::Error::custom(format!(
+                    "A modal must have between 1 and {MODAL_INPUTS_MAX} inputs"
+                ))
+                .with_span(ident),
+            );
+        }
+        let inputs = fields
+            .iter()
+            .filter_map(|input| {
+                errors.handle(input.validate());
+                let required = errors.handle(input.is_required())?;
+
+                Some((
+                    input.ident.as_ref(),
+                    input.generate_text_input(required),
+                    input.generate_parser(required),
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        if let Err(error) = errors.finish() {
+            tokens.extend(error.write_errors());
+            return;
+        }
+
+        let title = &self.title;
+        let input_idents = inputs.iter().map(|(ident, _, _)| ident);
+        let text_inputs = inputs.iter().map(|(_, text_input, _)| text_input);
+        let parsers = inputs.iter().map(|(_, _, parser)| parser);
+
+        tokens.extend(quote! {
+            impl bouncer_framework::component::modal::ModalData for #ident {
+                const MODAL_ID: &'static str = #modal_id;
+
+                fn modal() -> bouncer_framework::exts::interaction::InteractionModal {
+                    bouncer_framework::exts::interaction::InteractionModal::new(#modal_id, #title)
+                        #(.text_input(#text_inputs))*
+                }
+
+                fn parse_modal_data(
+                    data: &twilight_model::application::interaction::modal::ModalInteractionData,
+                ) -> Result<Self, bouncer_framework::component::modal::ModalError> {
+                    if data.custom_id != Self::MODAL_ID {
+                        return Err(bouncer_framework::component::modal::ModalError::UnexpectedModal(
+                            data.custom_id.clone(),
+                        ));
+                    }
+
+                    Ok(Self {
+                        #(#input_idents: #parsers,)*
+                    })
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::{ToTokens as _, format_ident};
+
+    use super::*;
+
+    fn expand(input: &syn::DeriveInput) -> String {
+        match Modal::from_derive_input(input) {
+            Ok(modal) => modal.to_token_stream(),
+            Err(error) => error.write_errors(),
+        }
+        .to_string()
+    }
+
+    #[test]
+    fn test_modal_inputs() {
+        let expanded = expand(&syn::parse_quote! {
+            #[modal(id = "report", title = "Report")]
+            struct Report {
+                #[input(label = "Reason", max_length = 100)]
+                reason: String,
+                #[input(label = "Details", style = "paragraph", placeholder = "Optional")]
+                details: Option<String>,
+            }
+        });
+
+        assert!(!expanded.contains("compile_error"));
+        assert!(expanded.contains("InteractionModal :: new (\"report\" , \"Report\")"));
+        assert!(expanded.contains("max_length : Some (100u16)"));
+        assert!(expanded.contains("TextInputStyle :: Paragraph"));
+        assert!(expanded.contains("reason : bouncer_framework :: component :: modal :: parse_required_input (data , \"reason\") ?"));
+        assert!(expanded.contains("details : bouncer_framework :: component :: modal :: parse_optional_input (data , \"details\")"));
+    }
+
+    #[test]
+    fn test_reject_invalid_inputs() {
+        let expanded = expand(&syn::parse_quote! {
+            #[modal(title = "")]
+            struct Report {
+                #[input(label = "Count")]
+                count: u32,
+                #[input(label = "Reason", min_length = 10, max_length = 5)]
+                reason: String,
+            }
+        });
+
+        assert!(expanded.contains("Modal title must be between 1 and 45 characters long"));
+        assert!(expanded.contains("Inputs must be a `String` or `Option<String>`"));
+        assert!(expanded.contains("`min_length` must not be greater than `max_length`"));
+    }
+
+    #[test]
+    fn test_too_many_inputs() {
+        let inputs = (0..=MODAL_INPUTS_MAX).map(|index| {
+            let ident = format_ident!("input_{index}");
+
+            quote! {
+                #[input(label = "Input")]
+                #ident: String
+            }
+        });
+        let expanded = expand(&syn::parse_quote! {
+            #[modal(title = "Many")]
+            struct Many {
+                #(#inputs),*
+            }
+        });
+
+        assert!(expanded.contains("A modal must have between 1 and 5 inputs"));
+    }
+}
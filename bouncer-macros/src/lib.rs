@@ -1,6 +1,7 @@
 use darling::FromDeriveInput as _;
 use quote::quote;
 
+mod attribute;
 mod derive;
 
 #[proc_macro_derive(Command, attributes(command, option))]
@@ -20,3 +21,107 @@ pub fn command_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
     }
     .into()
 }
+
+#[proc_macro_derive(Commands, attributes(command))]
+pub fn commands_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = match syn::parse2::<syn::DeriveInput>(input.into()) {
+        Ok(input) => input,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
+    let commands = match derive::commands::Commands::from_derive_input(&input) {
+        Ok(commands) => commands,
+        Err(error) => return error.write_errors().into(),
+    };
+
+    quote! {
+        #commands
+    }
+    .into()
+}
+
+#[proc_macro_derive(CommandChoice, attributes(choice))]
+pub fn command_choice_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = match syn::parse2::<syn::DeriveInput>(input.into()) {
+        Ok(input) => input,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
+    let command_choice = match derive::choice::CommandChoice::from_derive_input(&input) {
+        Ok(command_choice) => command_choice,
+        Err(error) => return error.write_errors().into(),
+    };
+
+    quote! {
+        #command_choice
+    }
+    .into()
+}
+
+#[proc_macro_derive(Component, attributes(component))]
+pub fn component_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = match syn::parse2::<syn::DeriveInput>(input.into()) {
+        Ok(input) => input,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
+    let component = match derive::component::Component::from_derive_input(&input) {
+        Ok(component) => component,
+        Err(error) => return error.write_errors().into(),
+    };
+
+    quote! {
+        #component
+    }
+    .into()
+}
+
+#[proc_macro_derive(Modal, attributes(modal, input))]
+pub fn modal_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = match syn::parse2::<syn::DeriveInput>(input.into()) {
+        Ok(input) => input,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
+    let modal = match derive::modal::Modal::from_derive_input(&input) {
+        Ok(modal) => modal,
+        Err(error) => return error.write_errors().into(),
+    };
+
+    quote! {
+        #modal
+    }
+    .into()
+}
+
+/// Implements `used_event_flags` and `required_intents` on an `EventHandler`
+/// implementation from the events it handles.
+#[proc_macro_attribute]
+pub fn event_handler(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    if !args.is_empty() {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`#[event_handler]` takes no arguments",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let input = match syn::parse2::<syn::ItemImpl>(input.into()) {
+        Ok(input) => input,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
+    let event_handler = match attribute::event_handler::EventHandler::from_item_impl(input) {
+        Ok(event_handler) => event_handler,
+        Err(error) => return error.write_errors().into(),
+    };
+
+    quote! {
+        #event_handler
+    }
+    .into()
+}
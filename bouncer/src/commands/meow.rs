@@ -5,9 +5,8 @@ use bouncer_framework::{
 };
 use twilight_model::{
     application::interaction::Interaction,
-    http::interaction::{InteractionResponse, InteractionResponseType},
+    id::{Id, marker::UserMarker},
 };
-use twilight_util::builder::InteractionResponseDataBuilder;
 
 #[derive(Debug, bouncer_macros::Command)]
 #[command(name = "meow", description = "Meow!")]
@@ -16,8 +15,8 @@ pub struct MeowCommand {
     _string: String,
     #[option(description = "Test boolean option")]
     _boolean: bool,
-    // #[option(description = "Test user option")]
-    // _user: Id<UserMarker>,
+    #[option(description = "Test user option")]
+    _user: Id<UserMarker>,
 }
 
 #[async_trait::async_trait]
@@ -27,15 +26,7 @@ impl Command for MeowCommand {
         context: &Context,
         interaction: &Interaction,
     ) -> Result<(), CommandExecuteError> {
-        interaction
-            .test(
-                &context.http,
-                InteractionResponse {
-                    kind: InteractionResponseType::ChannelMessageWithSource,
-                    data: Some(InteractionResponseDataBuilder::new().content("uwu").build()),
-                },
-            )
-            .await?;
+        interaction.reply(context, "uwu").await?;
 
         Ok(())
     }
@@ -1,4 +1,5 @@
-use bouncer_framework::Client;
+use bouncer_framework::{Client, command::CommandRegistry, session::FileSessionStore};
+use twilight_cache_inmemory::ResourceType;
 
 use crate::event_handler::Events;
 
@@ -13,11 +14,64 @@ async fn main() -> anyhow::Result<()> {
     let cli = bouncer_cli::Cli::parse_and_validate()?;
     let config = bouncer_config::Config::parse(&cli.config)?;
 
-    let mut client = Client::builder(&config.discord.token)
-        .event_handler(Events)
-        .try_build()?;
+    let command_registry = CommandRegistry::new().commands::<commands::Commands>();
+    let mut client_builder = Client::builder(&config.discord.token)
+        .event_handler(Events::new(command_registry.all_commands().to_vec()))
+        .command_registry(command_registry);
+    if let Some(session_path) = config.discord.session_path {
+        client_builder = client_builder.session_store(FileSessionStore::new(session_path));
+    }
+    if !config.cache.enabled {
+        client_builder = client_builder.disable_cache();
+    } else if let Some(resource_types) = &config.cache.resource_types {
+        client_builder = client_builder.cache_resource_types(parse_resource_types(resource_types)?);
+    }
+    if let Some(message_cache_size) = config.cache.message_cache_size {
+        client_builder = client_builder.message_cache_size(message_cache_size);
+    }
+    let client = client_builder.try_build()?;
 
-    client.start().await;
+    let shutdown = client.shutdown_handle();
+    tokio::spawn(async move {
+        if let Err(error) = shutdown_signal().await {
+            tracing::error!(source = ?error, "failed to listen for shutdown signals");
+
+            return;
+        }
+
+        tracing::info!("shutting down");
+        shutdown.shutdown();
+    });
+
+    client.start().await?;
 
     Ok(())
 }
+
+/// Parses cache resource type names, such as `guild` or `voice_state`.
+fn parse_resource_types(names: &[String]) -> anyhow::Result<ResourceType> {
+    names
+        .iter()
+        .try_fold(ResourceType::empty(), |resource_types, name| {
+            ResourceType::from_name(&name.to_uppercase())
+                .map(|resource_type| resource_types | resource_type)
+                .ok_or_else(|| anyhow::anyhow!("unknown cache resource type `{name}`"))
+        })
+}
+
+/// Waits for Ctrl+C, or for SIGTERM on Unix.
+async fn shutdown_signal() -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let mut terminate = signal(SignalKind::terminate())?;
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => result,
+            _ = terminate.recv() => Ok(()),
+        }
+    }
+
+    #[cfg(not(unix))]
+    tokio::signal::ctrl_c().await
+}